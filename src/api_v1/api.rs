@@ -1,18 +1,33 @@
-use super::models;
 use crate::api_v1::req::Query;
 
 use paste::paste;
 
-/// Generate constant endpoint
+/// Generate endpoint enum
 macro_rules! generate_endpoint {
   ($($ep:ident;)+) => {
     paste!{
-      pub struct ApiEndpoint;
-      impl ApiEndpoint {
+      /// API endpoints, the endpoint URL is derived from a base URL, so that requests can be sent to
+      /// private servers that provide the same `/api/get_*` routes.
+      #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+      pub enum ApiEndpoint {
         $(
-          pub const [<$ep:upper>]: &'static str = concat!("https://osu.ppy.sh/api/", stringify!($ep));
+          [<$ep:camel>],
         )+
       }
+
+      impl ApiEndpoint {
+        /// Base URL of the official osu! server
+        pub const DEFAULT_BASE_URL: &'static str = "https://osu.ppy.sh/api/";
+
+        /// Name of the endpoint, like `get_beatmaps`
+        pub fn as_str(&self) -> &'static str {
+          match self {
+            $(
+              Self::[<$ep:camel>] => stringify!($ep),
+            )+
+          }
+        }
+      }
     }
   };
 }
@@ -23,16 +38,70 @@ generate_endpoint! {
   get_scores;
   get_user_best;
   get_user_recent;
+  get_match;
+  get_replay;
+}
+
+impl ApiEndpoint {
+  /// Join the endpoint name to the given base URL. Base URL without trailing slash like
+  /// `https://example.com/api` is treated as a directory.
+  pub fn url(&self, base: &reqwest::Url) -> Result<reqwest::Url, Error> {
+    let url = if base.path().ends_with('/') {
+      base.join(self.as_str())?
+    } else {
+      let mut base = base.clone();
+      base.set_path(&format!("{}/", base.path()));
+      base.join(self.as_str())?
+    };
+
+    Ok(url)
+  }
+}
+
+impl std::fmt::Display for ApiEndpoint {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
   #[error("the given request param is invalid")]
   InvalidRequestParams,
+  #[error("fail to build request URL")]
+  InvalidUrl(#[from] url::ParseError),
   #[error("fail to send request")]
   NetIO(#[from] reqwest::Error),
+  #[error("the given API key is invalid")]
+  InvalidApiKey,
+  #[error("replay is not available: {0}")]
+  ReplayUnavailable(String),
+  #[error("request too frequently, the API server refuse to respond")]
+  RateLimited {
+    /// Time to wait before next request, from the `Retry-After` response header
+    retry_after: Option<std::time::Duration>,
+  },
+  #[error("the API server respond with error status {0}")]
+  ServerError(reqwest::StatusCode),
+  #[error("the API server respond with error: {0}")]
+  Api(String),
   #[error("fail to deserialize response into expected type")]
   UnexpectedResponse(#[from] serde_json::Error),
+  #[error("fail to read or write the cassette file")]
+  Cassette(#[source] std::io::Error),
+}
+
+impl Error {
+  /// Whether the error is caused by a temporary failure, like rate limiting, server error or
+  /// network error, so that the same request may succeed on retry.
+  pub fn is_transient(&self) -> bool {
+    match self {
+      Self::RateLimited { .. } => true,
+      Self::ServerError(status) => status.is_server_error(),
+      Self::NetIO(err) => err.is_timeout() || err.is_connect() || err.is_request() || err.is_body(),
+      _ => false,
+    }
+  }
 }
 
 macro_rules! generate_trait {
@@ -47,7 +116,22 @@ macro_rules! generate_trait {
   }
 }
 
-generate_trait! {
-  get_user_recent -> Vec<models::GetUserRecentResp>,
-  get_beatmaps    -> Vec<models::GetBeatmapsResp>
+/// Call the given macro with every API method and its response type, so that the trait and
+/// its implementations are generated from the same list.
+macro_rules! with_endpoints {
+  ($mac:ident) => {
+    $mac! {
+      get_user_recent -> Vec<$crate::api_v1::GetUserRecentResp>,
+      get_beatmaps    -> Vec<$crate::api_v1::GetBeatmapsResp>,
+      get_user        -> Vec<$crate::api_v1::GetUserResp>,
+      get_user_best   -> Vec<$crate::api_v1::GetUserBestResp>,
+      get_scores      -> Vec<$crate::api_v1::GetScoresResp>,
+      get_match       -> $crate::api_v1::GetMatchResp,
+      get_replay      -> $crate::api_v1::GetReplayResp
+    }
+  };
 }
+
+pub(crate) use with_endpoints;
+
+with_endpoints!(generate_trait);
@@ -0,0 +1,340 @@
+use std::{
+  collections::HashMap,
+  path::PathBuf,
+  sync::Mutex,
+  time::{Duration, SystemTime},
+};
+
+use base64::Engine;
+use tokio::time::Instant;
+use typed_builder::TypedBuilder;
+
+use crate::api_v1::{
+  api::with_endpoints, Approval, Error, GetBeatmapsResp, OsuApiRequester, Query,
+};
+
+/// Storage of the cached responses. Values are serialized responses, and should be dropped
+/// after the given TTL.
+#[async_trait::async_trait]
+pub trait CacheBackend {
+  async fn get(&self, key: &str) -> Option<Vec<u8>>;
+  async fn put(&self, key: &str, value: Vec<u8>, ttl: Duration);
+}
+
+/// Keep cached responses in memory. Expired entries are dropped on the next write.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+  entries: Mutex<HashMap<String, (Instant, Vec<u8>)>>,
+}
+
+impl MemoryCache {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for MemoryCache {
+  async fn get(&self, key: &str) -> Option<Vec<u8>> {
+    let mut entries = self.entries.lock().unwrap();
+    match entries.get(key) {
+      Some((expires_at, value)) if *expires_at > Instant::now() => Some(value.clone()),
+      Some(_) => {
+        entries.remove(key);
+        None
+      }
+      None => None,
+    }
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+    let mut entries = self.entries.lock().unwrap();
+    // Drop the expired entries, otherwise keys never read again stay forever
+    let now = Instant::now();
+    entries.retain(|_, (expires_at, _)| *expires_at > now);
+    entries.insert(key.to_string(), (Instant::now() + ttl, value));
+  }
+}
+
+/// Keep cached responses as files under the given directory, so that the cache survives
+/// restarts. Each file starts with a line of the expiry time in unix seconds.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+  dir: PathBuf,
+}
+
+impl DiskCache {
+  /// The directory will be created on the first write
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  fn path(&self, key: &str) -> PathBuf {
+    let name = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(key);
+    self.dir.join(name)
+  }
+}
+
+fn unix_now() -> u64 {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap_or_default()
+    .as_secs()
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for DiskCache {
+  async fn get(&self, key: &str) -> Option<Vec<u8>> {
+    let path = self.path(key);
+    let content = tokio::fs::read(&path).await.ok()?;
+    let split = content.iter().position(|b| *b == b'\n')?;
+    let expires_at: u64 = std::str::from_utf8(&content[..split]).ok()?.parse().ok()?;
+
+    if expires_at <= unix_now() {
+      let _ = tokio::fs::remove_file(&path).await;
+      return None;
+    }
+
+    Some(content[split + 1..].to_vec())
+  }
+
+  async fn put(&self, key: &str, value: Vec<u8>, ttl: Duration) {
+    // Cache is best effort, failing to write only causes a cache miss later
+    if tokio::fs::create_dir_all(&self.dir).await.is_err() {
+      return;
+    }
+    let mut content = format!("{}\n", unix_now() + ttl.as_secs()).into_bytes();
+    content.extend(value);
+    let _ = tokio::fs::write(self.path(key), content).await;
+  }
+}
+
+/// Decide how long the beatmaps will be cached, by their approval state.
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct CachePolicy {
+  /// TTL for ranked, approved and loved beatmaps, which rarely changes
+  #[builder(default = Duration::from_secs(7 * 24 * 60 * 60))]
+  ranked_ttl: Duration,
+  /// TTL for other beatmaps, which may be updated by the mapper anytime
+  #[builder(default = Duration::from_secs(10 * 60))]
+  unranked_ttl: Duration,
+}
+
+impl Default for CachePolicy {
+  fn default() -> Self {
+    Self::builder().build()
+  }
+}
+
+impl CachePolicy {
+  /// TTL of the whole response, which is the shortest TTL of the beatmaps in it
+  pub fn ttl(&self, maps: &[GetBeatmapsResp]) -> Duration {
+    if maps.is_empty() {
+      return self.unranked_ttl;
+    }
+
+    maps
+      .iter()
+      .map(|map| match map.approved {
+        Approval::Ranked | Approval::Approved | Approval::Loved => self.ranked_ttl,
+        _ => self.unranked_ttl,
+      })
+      .min()
+      .unwrap_or(self.unranked_ttl)
+  }
+}
+
+/// Cache key of the query, which ignores the API key and the order of the params
+fn cache_key(endpoint: &str, query: &Query) -> String {
+  let mut pairs: Vec<_> = query.pairs().iter().filter(|(key, _)| key != "k").collect();
+  pairs.sort();
+
+  let query = url::form_urlencoded::Serializer::new(String::new())
+    .extend_pairs(pairs)
+    .finish();
+  format!("{endpoint}?{query}")
+}
+
+/// Wrap an [`OsuApiRequester`] to cache the responses of get_beatmaps. Other requests are
+/// sent directly.
+#[derive(Debug)]
+pub struct Cached<R, B = MemoryCache> {
+  inner: R,
+  backend: B,
+  policy: CachePolicy,
+}
+
+impl<R> Cached<R> {
+  /// Cache in memory with the default policy
+  pub fn new(inner: R) -> Self {
+    Self::with_backend(inner, MemoryCache::new(), CachePolicy::default())
+  }
+}
+
+impl<R, B> Cached<R, B> {
+  pub fn with_backend(inner: R, backend: B, policy: CachePolicy) -> Self {
+    Self {
+      inner,
+      backend,
+      policy,
+    }
+  }
+
+  pub fn inner(&self) -> &R {
+    &self.inner
+  }
+
+  pub fn backend(&self) -> &B {
+    &self.backend
+  }
+}
+
+impl<R, B> Cached<R, B>
+where
+  R: OsuApiRequester + Send + Sync,
+  B: CacheBackend + Send + Sync,
+{
+  async fn cached_get_beatmaps(&self, query: Query) -> Result<Vec<GetBeatmapsResp>, Error> {
+    let key = cache_key("get_beatmaps", &query);
+    if let Some(value) = self.backend.get(&key).await {
+      // Entry written by other version may fail to deserialize, treat it as a miss
+      if let Ok(maps) = serde_json::from_slice(&value) {
+        return Ok(maps);
+      }
+    }
+
+    let maps = self.inner.get_beatmaps(&query).await?;
+    let value = serde_json::to_vec(&maps)?;
+    self.backend.put(&key, value, self.policy.ttl(&maps)).await;
+
+    Ok(maps)
+  }
+}
+
+macro_rules! cached_body {
+  ($this:ident, get_beatmaps, $query:ident) => {{
+    let query: Query = $query.try_into()?;
+    $this.cached_get_beatmaps(query).await
+  }};
+
+  ($this:ident, $name:ident, $query:ident) => {
+    $this.inner.$name($query).await
+  };
+}
+
+macro_rules! impl_cached {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    #[async_trait::async_trait]
+    impl<R, B> OsuApiRequester for Cached<R, B>
+    where
+      R: OsuApiRequester + Send + Sync,
+      B: CacheBackend + Send + Sync,
+    {
+      $(
+        async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+        where Q: TryInto<Query, Error = Error> + Send + Sync
+        {
+          cached_body!(self, $name, query)
+        }
+      )+
+    }
+  };
+}
+
+with_endpoints!(impl_cached);
+
+#[test]
+fn test_cache_key() {
+  let mut a = Query::new();
+  a.push("k", "key-a");
+  a.push("b", 1872396);
+  a.push("mods", 66);
+
+  let mut b = Query::new();
+  b.push("mods", 66);
+  b.push("b", 1872396);
+  b.push("k", "key-b");
+
+  assert_eq!(
+    cache_key("get_beatmaps", &a),
+    "get_beatmaps?b=1872396&mods=66"
+  );
+  assert_eq!(cache_key("get_beatmaps", &a), cache_key("get_beatmaps", &b));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_memory_cache_expiry() {
+  let cache = MemoryCache::new();
+  cache
+    .put("key", b"value".to_vec(), Duration::from_secs(60))
+    .await;
+  assert_eq!(cache.get("key").await.as_deref(), Some(&b"value"[..]));
+
+  tokio::time::advance(Duration::from_secs(61)).await;
+  assert!(cache.get("key").await.is_none());
+
+  // expired entries are swept on put, even if never read again
+  cache
+    .put("a", b"value".to_vec(), Duration::from_secs(60))
+    .await;
+  tokio::time::advance(Duration::from_secs(61)).await;
+  cache
+    .put("b", b"value".to_vec(), Duration::from_secs(60))
+    .await;
+  let entries = cache.entries.lock().unwrap();
+  assert!(!entries.contains_key("a"));
+  assert_eq!(entries.len(), 1);
+}
+
+#[tokio::test]
+async fn test_disk_cache() {
+  let dir = std::env::temp_dir().join(format!("osu-api-cache-{}", std::process::id()));
+  let cache = DiskCache::new(&dir);
+
+  cache
+    .put("get_beatmaps?b=1", b"[]".to_vec(), Duration::from_secs(60))
+    .await;
+  assert_eq!(
+    cache.get("get_beatmaps?b=1").await.as_deref(),
+    Some(&b"[]"[..])
+  );
+  assert!(cache.get("get_beatmaps?b=2").await.is_none());
+
+  // expired entry
+  cache
+    .put("get_beatmaps?b=3", b"[]".to_vec(), Duration::ZERO)
+    .await;
+  assert!(cache.get("get_beatmaps?b=3").await.is_none());
+
+  let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_cached() {
+  use crate::api_v1::GetBeatmapsProps;
+  use crate::stub::{stub_v1_client, StubResponse};
+
+  let (server, client) = stub_v1_client(|req| {
+    if !req.path.starts_with("/api/get_beatmaps") {
+      return StubResponse::json(200, r#"{"match": 0, "games": []}"#);
+    }
+    StubResponse::json(200, include_str!("../../tests/fixtures/get_beatmaps.json"))
+  })
+  .await;
+  let client = Cached::new(client);
+
+  for _ in 0..2 {
+    let props = GetBeatmapsProps::builder().beatmap_id(1872396).build();
+    let resp = client.get_beatmaps(props).await.unwrap();
+    assert_eq!(resp[0].beatmap_id, 1872396);
+  }
+  assert_eq!(server.requests().len(), 1);
+
+  // other endpoints are not cached
+  for _ in 0..2 {
+    let _ = client
+      .get_match(crate::api_v1::GetMatchProp::builder().match_id(1).build())
+      .await;
+  }
+  assert_eq!(server.requests().len(), 3);
+}
@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+
+use paste::paste;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::api_v1::{
+  api::with_endpoints,
+  req::{fetch, RawResponse},
+  ApiEndpoint, Error, OsuApiRequester, Query,
+};
+
+/// Whether the [`Cassette`] sends requests or reads the recorded responses
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+  /// Send requests to the API and record the successful responses
+  Record,
+  /// Only read the recorded responses, requests without record fail with [`Error::Cassette`]
+  #[default]
+  Replay,
+  /// Read the recorded response if exists, otherwise send request and record it
+  Auto,
+}
+
+/// A requester that records the API responses into files, and replay them later without
+/// network or API key. Enabled by the `testing` feature, for running integration tests offline.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), osu_api::api_v1::Error> {
+/// use osu_api::api_v1::{Cassette, CassetteMode, GetBeatmapsProps, OsuApiRequester};
+///
+/// let cassette = Cassette::builder()
+///   .dir("tests/cassettes")
+///   .mode(CassetteMode::Auto)
+///   .build();
+/// let props = GetBeatmapsProps::builder()
+///   .api_key("YOUR_API_KEY")
+///   .beatmapset_id(896080)
+///   .build();
+/// let maps = cassette.get_beatmaps(props).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Each response is saved as a JSON file named by the endpoint and the query. The API key is
+/// scrubbed from the query, so the files are safe to commit, and replaying doesn't need a key.
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct Cassette {
+  /// Directory of the recorded files
+  #[builder(setter(into))]
+  dir: PathBuf,
+  #[builder(default)]
+  mode: CassetteMode,
+  #[builder(default)]
+  http: reqwest::Client,
+  #[builder(default = reqwest::Url::parse(ApiEndpoint::DEFAULT_BASE_URL).unwrap())]
+  base_url: reqwest::Url,
+}
+
+// Characters encoded in the file name. Everything but alphanumerics and `-` is encoded, so
+// that different queries never share the same file.
+const FILE_NAME: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-');
+
+/// Content of a recorded file
+#[derive(Serialize, Deserialize)]
+struct Record {
+  endpoint: String,
+  // query string without the API key
+  query: String,
+  status: u16,
+  body: serde_json::Value,
+}
+
+/// Remove the API key from the query
+fn scrub(query: &Query) -> Query {
+  let mut scrubbed = Query::new();
+  for (key, val) in query.pairs().iter().filter(|(key, _)| key != "k") {
+    scrubbed.push(key, val);
+  }
+  scrubbed
+}
+
+impl Cassette {
+  fn path(&self, endpoint: ApiEndpoint, query: &str) -> PathBuf {
+    let name = utf8_percent_encode(query, FILE_NAME);
+    self.dir.join(format!("{endpoint}-{name}.json"))
+  }
+
+  async fn replay(path: &Path) -> Result<RawResponse, Error> {
+    let content = tokio::fs::read(path).await.map_err(Error::Cassette)?;
+    let record: Record = serde_json::from_slice(&content)?;
+
+    Ok(RawResponse {
+      status: reqwest::StatusCode::from_u16(record.status)
+        .map_err(|_| Error::Api(format!("invalid status in cassette: {}", record.status)))?,
+      retry_after: None,
+      body: serde_json::to_vec(&record.body)?,
+    })
+  }
+
+  async fn record(
+    &self,
+    endpoint: ApiEndpoint,
+    query: Query,
+    path: &Path,
+  ) -> Result<RawResponse, Error> {
+    let scrubbed = scrub(&query).into_query_str();
+    let url = endpoint.url(&self.base_url)?;
+    let resp = fetch(&self.http, url, query).await?;
+
+    // Don't let errors like invalid API key or rate limiting overwrite the record
+    if resp.status.is_success() {
+      let record = Record {
+        endpoint: endpoint.to_string(),
+        query: scrubbed,
+        status: resp.status.as_u16(),
+        body: serde_json::from_slice(&resp.body)?,
+      };
+      tokio::fs::create_dir_all(&self.dir)
+        .await
+        .map_err(Error::Cassette)?;
+      tokio::fs::write(path, serde_json::to_vec_pretty(&record)?)
+        .await
+        .map_err(Error::Cassette)?;
+    }
+
+    Ok(resp)
+  }
+
+  async fn play<T: DeserializeOwned>(
+    &self,
+    endpoint: ApiEndpoint,
+    query: Query,
+  ) -> Result<T, Error> {
+    let path = self.path(endpoint, &scrub(&query).into_query_str());
+
+    let resp = match self.mode {
+      CassetteMode::Record => self.record(endpoint, query, &path).await?,
+      CassetteMode::Replay => Self::replay(&path).await?,
+      CassetteMode::Auto if path.exists() => Self::replay(&path).await?,
+      CassetteMode::Auto => self.record(endpoint, query, &path).await?,
+    };
+
+    resp.parse()
+  }
+}
+
+macro_rules! impl_cassette {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    paste! {
+      #[async_trait::async_trait]
+      impl OsuApiRequester for Cassette {
+        $(
+          async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+          where Q: TryInto<Query, Error = Error> + Send + Sync
+          {
+            let query: Query = query.try_into()?;
+            self.play(ApiEndpoint::[<$name:camel>], query).await
+          }
+        )+
+      }
+    }
+  };
+}
+
+with_endpoints!(impl_cassette);
+
+#[tokio::test]
+async fn test_cassette() {
+  use crate::stub::{StubResponse, StubServer};
+
+  let server = StubServer::start(|req| {
+    if req.path.contains("u=2") {
+      return StubResponse::json(401, r#"{"error": "Please provide a valid API key."}"#);
+    }
+    StubResponse::json(200, "[]")
+  })
+  .await;
+  let dir = std::env::temp_dir().join(format!("osu-api-cassette-{}", std::process::id()));
+  let cassette = |mode| {
+    Cassette::builder()
+      .dir(&dir)
+      .mode(mode)
+      .base_url(server.url("/api/"))
+      .build()
+  };
+
+  let query = |user: u64| {
+    let mut query = Query::new();
+    query.push("k", "secret-key");
+    query.push("u", user);
+    query
+  };
+
+  // record
+  let recorder = cassette(CassetteMode::Record);
+  let resp = recorder.get_user_recent(&query(1)).await.unwrap();
+  assert!(resp.is_empty());
+  assert!(matches!(
+    recorder.get_user_recent(&query(2)).await,
+    Err(Error::InvalidApiKey)
+  ));
+  assert_eq!(server.requests().len(), 2);
+
+  let path = dir.join("get_user_recent-u%3D1.json");
+  let content = std::fs::read_to_string(&path).unwrap();
+  assert!(!content.contains("secret-key"));
+  // failed response is not recorded
+  assert!(!dir.join("get_user_recent-u%3D2.json").exists());
+
+  // replay, without sending request
+  let player = cassette(CassetteMode::Replay);
+  let resp = player.get_user_recent(&query(1)).await.unwrap();
+  assert!(resp.is_empty());
+  assert!(matches!(
+    player.get_user_recent(&query(2)).await,
+    Err(Error::Cassette(_))
+  ));
+  assert_eq!(server.requests().len(), 2);
+
+  // auto only sends request without record
+  let auto = cassette(CassetteMode::Auto);
+  auto.get_user_recent(&query(1)).await.unwrap();
+  assert_eq!(server.requests().len(), 2);
+  auto.get_user_recent(&query(3)).await.unwrap();
+  assert_eq!(server.requests().len(), 3);
+
+  let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn test_cassette_path() {
+  let cassette = Cassette::builder().dir("cassettes").build();
+  let path = |user: &str| {
+    let mut query = Query::new();
+    query.push("u", user);
+    cassette.path(ApiEndpoint::GetUser, &query.into_query_str())
+  };
+
+  assert_eq!(path("a.b"), Path::new("cassettes/get_user-u%3Da%2Eb.json"));
+  assert_ne!(path("a.b"), path("a_b"));
+  assert_ne!(path("a b"), path("a+b"));
+}
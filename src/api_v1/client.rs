@@ -0,0 +1,321 @@
+use chrono::NaiveDate;
+use paste::paste;
+use typed_builder::TypedBuilder;
+
+use crate::api_v1::{
+  api::with_endpoints,
+  req::{send_request, Query},
+  ApiEndpoint, Error, GameMode, GetBeatmapsProps, GetBeatmapsResp, GetMatchProp, GetMatchResp,
+  GetReplayProp, GetReplayResp, GetScoresProp, GetScoresResp, GetUserBestProp, GetUserBestResp,
+  GetUserProp, GetUserRecentProp, GetUserRecentResp, GetUserResp, ModsFlag, OsuApiRequester,
+  UserId,
+};
+
+/// A client that owns the API key and the HTTP client, so that requests don't need to carry the
+/// API key themselves.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), osu_api::api_v1::Error> {
+/// use osu_api::api_v1::OsuClient;
+///
+/// let client = OsuClient::new("YOUR_API_KEY");
+/// let maps = client.beatmaps().set(896080).send().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// It also implements [`OsuApiRequester`], the `api_key` of the props can be omitted, and the
+/// client's API key will be used.
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct OsuClient {
+  #[builder(setter(into))]
+  api_key: String,
+  #[builder(default)]
+  http: reqwest::Client,
+  /// Base URL of the API, endpoint name like `get_beatmaps` will be joined to it. Default to
+  /// the official server. Private servers that provide the same `/api/get_*` routes can be used
+  /// by setting it to like `https://akatsuki.gg/api/`.
+  #[builder(default = reqwest::Url::parse(ApiEndpoint::DEFAULT_BASE_URL).unwrap())]
+  base_url: reqwest::Url,
+}
+
+impl OsuClient {
+  /// Create a client with default HTTP client and base URL
+  pub fn new(api_key: impl Into<String>) -> Self {
+    Self::builder().api_key(api_key).build()
+  }
+
+  /// Fill in the API key when the query doesn't carry one
+  fn with_key(&self, mut query: Query) -> Query {
+    if !query.pairs().iter().any(|(key, _)| key == "k") {
+      query.push("k", &self.api_key);
+    }
+    query
+  }
+
+  pub fn beatmaps(&self) -> BeatmapsRequest<'_, '_> {
+    BeatmapsRequest {
+      client: self,
+      props: GetBeatmapsProps::builder().build(),
+    }
+  }
+
+  pub fn user<'u>(&self, user: impl Into<UserId<'u>>) -> UserRequest<'_, 'u> {
+    UserRequest {
+      client: self,
+      props: GetUserProp::builder().user_info(user).build(),
+    }
+  }
+
+  pub fn user_recent<'u>(&self, user: impl Into<UserId<'u>>) -> UserRecentRequest<'_, 'u> {
+    UserRecentRequest {
+      client: self,
+      props: GetUserRecentProp::builder().user_info(user).build(),
+    }
+  }
+
+  pub fn user_best<'u>(&self, user: impl Into<UserId<'u>>) -> UserBestRequest<'_, 'u> {
+    UserBestRequest {
+      client: self,
+      props: GetUserBestProp::builder().user_info(user).build(),
+    }
+  }
+
+  pub fn scores(&self, beatmap_id: u64) -> ScoresRequest<'_, '_> {
+    ScoresRequest {
+      client: self,
+      props: GetScoresProp::builder().beatmap_id(beatmap_id).build(),
+    }
+  }
+
+  pub fn multiplayer(&self, match_id: u64) -> MatchRequest<'_> {
+    MatchRequest {
+      client: self,
+      props: GetMatchProp::builder().match_id(match_id).build(),
+    }
+  }
+
+  pub fn replay(&self) -> ReplayRequest<'_, '_> {
+    ReplayRequest {
+      client: self,
+      props: GetReplayProp::builder().build(),
+    }
+  }
+}
+
+macro_rules! impl_client {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    paste! {
+      #[async_trait::async_trait]
+      impl OsuApiRequester for OsuClient {
+        $(
+          async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+          where Q: TryInto<Query, Error = Error> + Send + Sync
+          {
+            let query = self.with_key(query.try_into()?);
+            let url = ApiEndpoint::[<$name:camel>].url(&self.base_url)?;
+            send_request(&self.http, url, query).await
+          }
+        )+
+      }
+    }
+  };
+}
+
+with_endpoints!(impl_client);
+
+/// Generate setters that modify the inner props and return self
+macro_rules! setter {
+  ( $( $(#[$doc:meta])* $method:ident($($arg:ident: $ty:ty)?) => $field:ident = $val:expr; )+ ) => {
+    $(
+      $(#[$doc])*
+      pub fn $method(mut self $(, $arg: $ty)?) -> Self {
+        self.props.$field = $val;
+        self
+      }
+    )+
+  };
+}
+
+/// Request to get_beatmaps API, created by [`OsuClient::beatmaps`]
+pub struct BeatmapsRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetBeatmapsProps<'u, 'c>,
+}
+
+impl<'c, 'u> BeatmapsRequest<'c, 'u> {
+  setter! {
+    /// Specify a beatmapset id
+    set(id: u64) => beatmapset_id = id;
+    /// Specify a beatmap id
+    map(id: u64) => beatmap_id = id;
+    /// Only return beatmaps created by the given user
+    user(user: impl Into<UserId<'u>>) => user_id = Some(user.into());
+    mode(mode: GameMode) => mode = Some(mode);
+    /// Include converted beatmaps when mode is not standard
+    include_converted() => include_converted = true;
+    /// Specify the md5 hash of the beatmap
+    hash(hash: impl Into<String>) => beatmap_hash = Some(hash.into());
+    limit(limit: u32) => limit = limit;
+    /// Get difficulty values with the given mods applied
+    mods(mods: Vec<ModsFlag>) => mods = mods;
+    /// Only return beatmaps ranked or loved since the given date
+    since(date: NaiveDate) => since = Some(date);
+  }
+
+  pub async fn send(self) -> Result<Vec<GetBeatmapsResp>, Error> {
+    self.client.get_beatmaps(self.props).await
+  }
+}
+
+/// Request to get_user API, created by [`OsuClient::user`]
+pub struct UserRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetUserProp<'c, 'u>,
+}
+
+impl<'c, 'u> UserRequest<'c, 'u> {
+  setter! {
+    mode(mode: GameMode) => mode = Some(mode);
+    /// Max number of days between now and last event date. Range of 1-31.
+    event_days(days: u8) => event_days = Some(days);
+  }
+
+  pub async fn send(self) -> Result<Vec<GetUserResp>, Error> {
+    self.client.get_user(self.props).await
+  }
+}
+
+/// Request to get_user_recent API, created by [`OsuClient::user_recent`]
+pub struct UserRecentRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetUserRecentProp<'c, 'u>,
+}
+
+impl<'c, 'u> UserRecentRequest<'c, 'u> {
+  setter! {
+    mode(mode: GameMode) => mode = Some(mode);
+    limit(limit: u8) => limit = limit;
+  }
+
+  pub async fn send(self) -> Result<Vec<GetUserRecentResp>, Error> {
+    self.client.get_user_recent(self.props).await
+  }
+}
+
+/// Request to get_user_best API, created by [`OsuClient::user_best`]
+pub struct UserBestRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetUserBestProp<'c, 'u>,
+}
+
+impl<'c, 'u> UserBestRequest<'c, 'u> {
+  setter! {
+    mode(mode: GameMode) => mode = Some(mode);
+    /// Amount of results. Range between 1 and 100.
+    limit(limit: u8) => limit = limit;
+  }
+
+  pub async fn send(self) -> Result<Vec<GetUserBestResp>, Error> {
+    self.client.get_user_best(self.props).await
+  }
+}
+
+/// Request to get_scores API, created by [`OsuClient::scores`]
+pub struct ScoresRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetScoresProp<'c, 'u>,
+}
+
+impl<'c, 'u> ScoresRequest<'c, 'u> {
+  setter! {
+    /// Only return scores from the given user
+    user(user: impl Into<UserId<'u>>) => user_info = Some(user.into());
+    mode(mode: GameMode) => mode = Some(mode);
+    /// Only return scores played with exactly the given mods combination
+    mods(mods: Vec<ModsFlag>) => mods = mods;
+    /// Amount of results. Range between 1 and 100.
+    limit(limit: u8) => limit = limit;
+  }
+
+  pub async fn send(self) -> Result<Vec<GetScoresResp>, Error> {
+    self.client.get_scores(self.props).await
+  }
+}
+
+/// Request to get_match API, created by [`OsuClient::multiplayer`]
+pub struct MatchRequest<'c> {
+  client: &'c OsuClient,
+  props: GetMatchProp<'c>,
+}
+
+impl<'c> MatchRequest<'c> {
+  pub async fn send(self) -> Result<GetMatchResp, Error> {
+    self.client.get_match(self.props).await
+  }
+}
+
+/// Request to get_replay API, created by [`OsuClient::replay`]. Either `score` or the
+/// combination of `map`, `user` and `mode` is required.
+pub struct ReplayRequest<'c, 'u> {
+  client: &'c OsuClient,
+  props: GetReplayProp<'c, 'u>,
+}
+
+impl<'c, 'u> ReplayRequest<'c, 'u> {
+  setter! {
+    score(id: u64) => score_id = Some(id);
+    map(id: u64) => beatmap_id = Some(id);
+    user(user: impl Into<UserId<'u>>) => user_info = Some(user.into());
+    mode(mode: GameMode) => mode = Some(mode);
+    mods(mods: Vec<ModsFlag>) => mods = mods;
+  }
+
+  pub async fn send(self) -> Result<GetReplayResp, Error> {
+    self.client.get_replay(self.props).await
+  }
+}
+
+#[test]
+fn test_with_key() {
+  let client = OsuClient::new("client-key");
+
+  let query = Query::try_from(GetMatchProp::builder().match_id(1).build()).unwrap();
+  assert_eq!(client.with_key(query).into_query_str(), "mp=1&k=client-key");
+
+  let props = GetMatchProp::builder()
+    .api_key("my-key")
+    .match_id(1)
+    .build();
+  let query = Query::try_from(props).unwrap();
+  assert_eq!(client.with_key(query).into_query_str(), "k=my-key&mp=1");
+}
+
+#[tokio::test]
+async fn test_private_server() {
+  use crate::stub::{StubResponse, StubServer};
+
+  let server = StubServer::start(|_| {
+    StubResponse::json(
+      200,
+      r#"[{"user_id": "1000", "username": "tester", "join_date": "2020-01-01 00:00:00",
+      "country": "JP", "events": []}]"#,
+    )
+  })
+  .await;
+
+  // base URL without trailing slash
+  let client = OsuClient::builder()
+    .api_key("stub-key")
+    .base_url(server.url("/api"))
+    .build();
+  let resp = client.user("tester").send().await.unwrap();
+  assert_eq!(resp[0].user_id, 1000);
+
+  let requests = server.requests();
+  assert_eq!(requests[0].method, "GET");
+  assert_eq!(
+    requests[0].path,
+    "/api/get_user?type=string&u=tester&k=stub-key"
+  );
+}
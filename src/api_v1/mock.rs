@@ -0,0 +1,178 @@
+use std::{
+  any::Any,
+  collections::{HashMap, VecDeque},
+  sync::Mutex,
+};
+
+use paste::paste;
+
+use crate::api_v1::{api::with_endpoints, ApiEndpoint, Error, OsuApiRequester, Query};
+
+/// Canned response of the [`MockRequester`]
+enum MockResponse {
+  Value(Box<dyn Any + Send>),
+  Json(String),
+  Error(Error),
+}
+
+/// An [`OsuApiRequester`] that doesn't send any request, but respond with the queued responses
+/// and records the queries it received. Enabled by the `testing` feature, for testing code
+/// generic over [`OsuApiRequester`].
+///
+/// ```
+/// # async fn run() {
+/// use osu_api::api_v1::{ApiEndpoint, Error, GetUserProp, MockRequester, OsuApiRequester};
+///
+/// let mock = MockRequester::new();
+/// mock.respond_with_json(ApiEndpoint::GetUserRecent, "[]");
+/// mock.respond_with_error(ApiEndpoint::GetUser, Error::InvalidApiKey);
+///
+/// let resp = mock.get_user(GetUserProp::builder().user_info("BlackDog5").build()).await;
+/// assert!(matches!(resp, Err(Error::InvalidApiKey)));
+/// assert_eq!(mock.requests_to(ApiEndpoint::GetUser).len(), 1);
+/// # }
+/// ```
+///
+/// Responses of the same endpoint are consumed in the queued order. Calling an endpoint without
+/// queued response, or queuing a value of other type than the endpoint returns, panics.
+#[derive(Default)]
+pub struct MockRequester {
+  responses: Mutex<HashMap<ApiEndpoint, VecDeque<MockResponse>>>,
+  requests: Mutex<Vec<(ApiEndpoint, Query)>>,
+}
+
+impl MockRequester {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn push(&self, endpoint: ApiEndpoint, resp: MockResponse) {
+    self
+      .responses
+      .lock()
+      .unwrap()
+      .entry(endpoint)
+      .or_default()
+      .push_back(resp);
+  }
+
+  /// Queue a typed response, like `Vec<GetBeatmapsResp>` for [`ApiEndpoint::GetBeatmaps`]
+  pub fn respond_with<T: Any + Send>(&self, endpoint: ApiEndpoint, value: T) {
+    self.push(endpoint, MockResponse::Value(Box::new(value)))
+  }
+
+  /// Queue a JSON response in the format the API respond with, it is deserialized when the
+  /// endpoint is called
+  pub fn respond_with_json(&self, endpoint: ApiEndpoint, json: impl Into<String>) {
+    self.push(endpoint, MockResponse::Json(json.into()))
+  }
+
+  pub fn respond_with_error(&self, endpoint: ApiEndpoint, err: Error) {
+    self.push(endpoint, MockResponse::Error(err))
+  }
+
+  /// All the queries received, in receiving order
+  pub fn requests(&self) -> Vec<(ApiEndpoint, Query)> {
+    self.requests.lock().unwrap().clone()
+  }
+
+  /// Queries received by the given endpoint, in receiving order
+  pub fn requests_to(&self, endpoint: ApiEndpoint) -> Vec<Query> {
+    self
+      .requests
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(ep, _)| *ep == endpoint)
+      .map(|(_, query)| query.clone())
+      .collect()
+  }
+
+  fn respond<T>(&self, endpoint: ApiEndpoint, query: Query) -> Result<T, Error>
+  where
+    T: Any + serde::de::DeserializeOwned,
+  {
+    self.requests.lock().unwrap().push((endpoint, query));
+
+    let resp = self
+      .responses
+      .lock()
+      .unwrap()
+      .get_mut(&endpoint)
+      .and_then(|queue| queue.pop_front())
+      .unwrap_or_else(|| panic!("MockRequester has no response queued for {endpoint}"));
+
+    match resp {
+      MockResponse::Value(value) => match value.downcast::<T>() {
+        Ok(value) => Ok(*value),
+        Err(_) => panic!(
+          "MockRequester got a response of wrong type for {endpoint}, expect {}",
+          std::any::type_name::<T>()
+        ),
+      },
+      MockResponse::Json(json) => Ok(serde_json::from_str(&json)?),
+      MockResponse::Error(err) => Err(err),
+    }
+  }
+}
+
+macro_rules! impl_mock {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    paste! {
+      #[async_trait::async_trait]
+      impl OsuApiRequester for MockRequester {
+        $(
+          async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+          where Q: TryInto<Query, Error = Error> + Send + Sync
+          {
+            let query: Query = query.try_into()?;
+            self.respond(ApiEndpoint::[<$name:camel>], query)
+          }
+        )+
+      }
+    }
+  };
+}
+
+with_endpoints!(impl_mock);
+
+#[tokio::test]
+async fn test_mock_requester() {
+  use crate::api_v1::{GetBeatmapsProps, GetBeatmapsResp, GetUserRecentProp};
+
+  let mock = MockRequester::new();
+
+  let maps: Vec<GetBeatmapsResp> =
+    serde_json::from_str(include_str!("../../tests/fixtures/get_beatmaps.json")).unwrap();
+  mock.respond_with(ApiEndpoint::GetBeatmaps, maps);
+  mock.respond_with_json(
+    ApiEndpoint::GetUserRecent,
+    r#"[{"beatmap_id": "1872396", "score": "1000000", "maxcombo": "1125", "count50": "0",
+    "count100": "2", "count300": "773", "countmiss": "0", "countkatu": "1",
+    "countgeki": "100", "perfect": "1", "enabled_mods": "0", "user_id": "1000",
+    "date": "2023-01-01 00:00:00", "rank": "S"}]"#,
+  );
+  mock.respond_with_error(
+    ApiEndpoint::GetUserRecent,
+    Error::RateLimited { retry_after: None },
+  );
+
+  let props = GetBeatmapsProps::builder().beatmap_id(1872396).build();
+  let maps = mock.get_beatmaps(props).await.unwrap();
+  assert_eq!(maps[0].beatmap_id, 1872396);
+
+  let props = GetUserRecentProp::builder().user_info(1000).build();
+  let scores = mock.get_user_recent(props).await.unwrap();
+  assert_eq!(scores[0].score, 1000000);
+
+  let props = GetUserRecentProp::builder().user_info(1000).build();
+  let resp = mock.get_user_recent(props).await;
+  assert!(matches!(resp, Err(Error::RateLimited { .. })));
+
+  assert_eq!(mock.requests().len(), 3);
+  let queries = mock.requests_to(ApiEndpoint::GetUserRecent);
+  assert_eq!(queries.len(), 2);
+  assert!(queries[0]
+    .pairs()
+    .contains(&("u".to_string(), "1000".to_string())));
+}
@@ -1,9 +1,31 @@
 mod api;
+mod cache;
+#[cfg(any(test, feature = "testing"))]
+mod cassette;
+mod client;
+#[cfg(any(test, feature = "testing"))]
+mod mock;
 mod models;
+mod ratelimit;
 mod req;
+mod retry;
 
 pub use api::{ApiEndpoint, Error, OsuApiRequester};
+pub use cache::{CacheBackend, CachePolicy, Cached, DiskCache, MemoryCache};
+#[cfg(any(test, feature = "testing"))]
+pub use cassette::{Cassette, CassetteMode};
+pub use client::{
+  BeatmapsRequest, MatchRequest, OsuClient, ReplayRequest, ScoresRequest, UserBestRequest,
+  UserRecentRequest, UserRequest,
+};
+#[cfg(any(test, feature = "testing"))]
+pub use mock::MockRequester;
 pub use models::{
-  GameMode, GetBeatmapsProps, GetBeatmapsResp, GetUserRecentProp, GetUserRecentResp, ModsFlag,
-  UserId,
+  Approval, GameMode, Genre, GetBeatmapsProps, GetBeatmapsResp, GetMatchProp, GetMatchResp,
+  GetReplayProp, GetReplayResp, GetScoresProp, GetScoresResp, GetUserBestProp, GetUserBestResp,
+  GetUserProp, GetUserRecentProp, GetUserRecentResp, GetUserResp, Language, MatchGame, MatchInfo,
+  MatchScore, ModsFlag, ScoringType, Team, TeamType, UserEvent, UserId,
 };
+pub use ratelimit::RateLimited;
+pub use req::Query;
+pub use retry::{Retry, RetryPolicy};
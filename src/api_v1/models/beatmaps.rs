@@ -1,33 +1,34 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
-use super::{de::*, GameMode, ModsFlag, UserId};
+use super::{de::*, fold_mods, ser::*, GameMode, Genre, Language, ModsFlag, UserId};
 use crate::api_v1::{req::Query, Error as ReqError};
 
 #[derive(Debug, TypedBuilder)]
 #[builder(builder_type_doc = "Builder for creating request to get_beatmaps API,
 read https://github.com/ppy/osu-api/wiki#parameters for meaning")]
 pub struct GetBeatmapsProps<'u, 'k> {
-  api_key: &'k str,
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
   #[builder(default = 0)]
-  beatmapset_id: u64,
+  pub(crate) beatmapset_id: u64,
   #[builder(default = 0)]
-  beatmap_id: u64,
+  pub(crate) beatmap_id: u64,
   #[builder(default, setter(transform = |id: impl Into<UserId<'u>>| Some(id.into())))]
-  user_id: Option<UserId<'u>>,
+  pub(crate) user_id: Option<UserId<'u>>,
   #[builder(default, setter(strip_option))]
-  mode: Option<GameMode>,
+  pub(crate) mode: Option<GameMode>,
   #[builder(setter(strip_bool))]
-  include_converted: bool,
+  pub(crate) include_converted: bool,
   #[builder(default, setter(strip_option))]
-  beatmap_hash: Option<String>,
+  pub(crate) beatmap_hash: Option<String>,
   #[builder(default = 0)]
-  limit: u32,
+  pub(crate) limit: u32,
   #[builder(default = Vec::new())]
-  mods: Vec<ModsFlag>,
+  pub(crate) mods: Vec<ModsFlag>,
   #[builder(default, setter(strip_option))]
-  since: Option<chrono::NaiveDate>,
+  pub(crate) since: Option<chrono::NaiveDate>,
 }
 
 impl<'u, 'k> TryFrom<GetBeatmapsProps<'u, 'k>> for Query {
@@ -36,7 +37,9 @@ impl<'u, 'k> TryFrom<GetBeatmapsProps<'u, 'k>> for Query {
   fn try_from(value: GetBeatmapsProps<'u, 'k>) -> std::result::Result<Self, Self::Error> {
     let mut query = Query::new();
 
-    query.push("k", value.api_key);
+    if let Some(key) = value.api_key {
+      query.push("k", key);
+    }
 
     if value.beatmapset_id == 0 && value.beatmap_id == 0 {
       return Err(ReqError::InvalidRequestParams);
@@ -63,7 +66,7 @@ impl<'u, 'k> TryFrom<GetBeatmapsProps<'u, 'k>> for Query {
     }
 
     if let Some(mode) = value.mode {
-      query.push("m", &mode);
+      query.push("m", mode);
 
       let include_converted = if value.include_converted { "1" } else { "0" };
       match mode {
@@ -81,12 +84,7 @@ impl<'u, 'k> TryFrom<GetBeatmapsProps<'u, 'k>> for Query {
     }
 
     if !value.mods.is_empty() {
-      let mods = value
-        .mods
-        .into_iter()
-        .fold(0_u64, |accum, item| accum | item.bits());
-
-      query.push("mods", mods);
+      query.push("mods", fold_mods(&value.mods));
     }
 
     if let Some(date) = value.since {
@@ -97,7 +95,8 @@ impl<'u, 'k> TryFrom<GetBeatmapsProps<'u, 'k>> for Query {
   }
 }
 
-#[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Approval {
   Graveyard = -2,
   WIP = -1,
@@ -108,93 +107,149 @@ pub enum Approval {
   Loved = 4,
 }
 
-/// TODO: convert all type
-#[derive(Deserialize)]
+// Display the numeric value used by the API
+impl std::fmt::Display for Approval {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", *self as i8)
+  }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GetBeatmapsResp {
   // 4 = loved, 3 = qualified, 2 = approved, 1 = ranked, 0 = pending, -1 = WIP, -2 = graveyard
-  #[serde(deserialize_with = "s_to_approval")]
+  #[serde(deserialize_with = "s_to_approval", serialize_with = "to_s")]
   pub approved: Approval,
   // date submitted, in UTC
-  #[serde(deserialize_with = "s_to_datetime")]
+  #[serde(deserialize_with = "s_to_datetime", serialize_with = "datetime_to_s")]
   pub submit_date: DateTime<Utc>,
-  // date ranked, in UTC
-  #[serde(deserialize_with = "s_to_datetime")]
-  pub approved_date: DateTime<Utc>,
+  // date ranked, in UTC. Null if the map is not ranked yet.
+  #[serde(
+    default,
+    deserialize_with = "s_to_opt_datetime",
+    serialize_with = "opt_datetime_to_s"
+  )]
+  pub approved_date: Option<DateTime<Utc>>,
   // last update date, in UTC. May be after approved_date if map was unranked and reranked.
-  #[serde(deserialize_with = "s_to_datetime")]
+  #[serde(deserialize_with = "s_to_datetime", serialize_with = "datetime_to_s")]
   pub last_update: DateTime<Utc>,
   pub artist: String,
   // beatmap_id is per difficulty
-  #[serde(deserialize_with = "s_to_u64")]
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
   pub beatmap_id: u64,
   // beatmapset_id groups difficulties into a set
-  #[serde(deserialize_with = "s_to_u64")]
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
   pub beatmapset_id: u64,
-  #[serde(deserialize_with = "s_to_u16")]
-  pub bpm: u16,
+  #[serde(deserialize_with = "s_to_f64", serialize_with = "to_s")]
+  pub bpm: f64,
   pub creator: String,
-  #[serde(deserialize_with = "s_to_u64")]
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
   pub creator_id: u64,
   // The number of stars the map would have in-game and on the website
-  #[serde(deserialize_with = "s_to_f64")]
+  #[serde(deserialize_with = "s_to_f64", serialize_with = "to_s")]
   pub difficultyrating: f64,
-  #[serde(deserialize_with = "s_to_f64")]
-  pub diff_aim: f64,
-  #[serde(deserialize_with = "s_to_f64")]
-  pub diff_speed: f64,
+  // Aim and speed difficulty are only calculated for osu!standard
+  #[serde(default, deserialize_with = "s_to_opt", serialize_with = "opt_to_s")]
+  pub diff_aim: Option<f64>,
+  #[serde(default, deserialize_with = "s_to_opt", serialize_with = "opt_to_s")]
+  pub diff_speed: Option<f64>,
   // Circle size value (CS)
-  #[serde(deserialize_with = "s_to_f32")]
+  #[serde(deserialize_with = "s_to_f32", serialize_with = "to_s")]
   pub diff_size: f32,
   // Overall difficulty (OD)
-  #[serde(deserialize_with = "s_to_f32")]
+  #[serde(deserialize_with = "s_to_f32", serialize_with = "to_s")]
   pub diff_overall: f32,
   // Approach Rate (AR)
-  #[serde(deserialize_with = "s_to_f32")]
+  #[serde(deserialize_with = "s_to_f32", serialize_with = "to_s")]
   pub diff_approach: f32,
   // Health drain (HP)
-  #[serde(deserialize_with = "s_to_f32")]
+  #[serde(deserialize_with = "s_to_f32", serialize_with = "to_s")]
   pub diff_drain: f32,
   // seconds from first note to last note not including breaks
-  #[serde(deserialize_with = "s_to_u64")]
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
   pub hit_length: u64,
   pub source: String,
-  pub genre_id: String,
-  // 0 = any, 1 = unspecified, 2 = english, 3 = japanese, 4 = chinese, 5 = instrumental, 6 = korean, 7 = french, 8 = german, 9 = swedish, 10 = spanish, 11 = italian, 12 = russian, 13 = polish, 14 = other
-  pub language_id: String,
+  #[serde(deserialize_with = "s_to_genre", serialize_with = "to_s")]
+  pub genre_id: Genre,
+  #[serde(deserialize_with = "s_to_language", serialize_with = "to_s")]
+  pub language_id: Language,
   // song name
   pub title: String,
   // seconds from first note to last note including breaks
-  pub total_length: String,
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
+  pub total_length: u64,
   // difficulty name
   pub version: String,
-  pub file_md5: String,
   // md5 hash of the beatmap
-  // game mode,
-  pub mode: String,
+  pub file_md5: String,
+  #[serde(deserialize_with = "s_to_game_mode", serialize_with = "to_s")]
+  pub mode: GameMode,
   // Beatmap tags separated by spaces.
   pub tags: String,
   // Number of times the beatmap was favourited. (Americans: notice the ou!)
-  pub favourite_count: String,
-  pub rating: String,
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
+  pub favourite_count: u64,
+  #[serde(deserialize_with = "s_to_f64", serialize_with = "to_s")]
+  pub rating: f64,
   // Number of times the beatmap was played
-  pub playcount: String,
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
+  pub playcount: u64,
   // Number of times the beatmap was passed, completed (the user didn't fail or retry)
-  pub passcount: String,
-  pub count_normal: String,
-  pub count_slider: String,
-  pub count_spinner: String,
-  // The maximum combo a user can reach playing this beatmap.
-  pub max_combo: String,
+  #[serde(deserialize_with = "s_to_u64", serialize_with = "to_s")]
+  pub passcount: u64,
+  #[serde(deserialize_with = "s_to_u32", serialize_with = "to_s")]
+  pub count_normal: u32,
+  #[serde(deserialize_with = "s_to_u32", serialize_with = "to_s")]
+  pub count_slider: u32,
+  #[serde(deserialize_with = "s_to_u32", serialize_with = "to_s")]
+  pub count_spinner: u32,
+  // The maximum combo a user can reach playing this beatmap. Null for some taiko and mania maps.
+  #[serde(default, deserialize_with = "s_to_opt", serialize_with = "opt_to_s")]
+  pub max_combo: Option<u32>,
   // If this beatmap has a storyboard
-  #[serde(deserialize_with = "s_to_bool")]
+  #[serde(deserialize_with = "s_to_bool", serialize_with = "bool_to_s")]
   pub storyboard: bool,
   // If this beatmap has a video
-  #[serde(deserialize_with = "s_to_bool")]
+  #[serde(deserialize_with = "s_to_bool", serialize_with = "bool_to_s")]
   pub video: bool,
   // If the download for this beatmap is unavailable (old map, etc.)
-  #[serde(deserialize_with = "s_to_bool")]
+  #[serde(deserialize_with = "s_to_bool", serialize_with = "bool_to_s")]
   pub download_unavailable: bool,
   // If the audio for this beatmap is unavailable (DMCA takedown, etc.)
-  #[serde(deserialize_with = "s_to_bool")]
+  #[serde(deserialize_with = "s_to_bool", serialize_with = "bool_to_s")]
   pub audio_unavailable: bool,
 }
+
+#[test]
+fn test_deserialize_beatmap() {
+  let resp = r#"[{
+    "beatmapset_id": "1262832", "beatmap_id": "2627380", "approved": "-1",
+    "total_length": "137", "hit_length": "134", "version": "Oni",
+    "file_md5": "7c8a1e0b8e9e5a1c9b7b6f7d95e1f8a2", "diff_size": "5", "diff_overall": "6",
+    "diff_approach": "5", "diff_drain": "5", "mode": "1", "count_normal": "621",
+    "count_slider": "16", "count_spinner": "3", "submit_date": "2020-09-21 09:44:48",
+    "approved_date": null, "last_update": "2020-10-02 11:18:01",
+    "artist": "Camellia", "artist_unicode": null, "title": "Exit This Earth's Atomosphere",
+    "title_unicode": null, "creator": "Mirash", "creator_id": "5006283", "bpm": "172.5",
+    "source": "", "tags": "taiko", "genre_id": "10", "language_id": "5",
+    "favourite_count": "31", "rating": "9.21154", "storyboard": "0", "video": "0",
+    "download_unavailable": "0", "audio_unavailable": "0", "playcount": "4312",
+    "passcount": "623", "packs": null, "max_combo": null, "diff_aim": null,
+    "diff_speed": null, "difficultyrating": "4.90725"
+  }]"#;
+  let resp: Vec<GetBeatmapsResp> = serde_json::from_str(resp).unwrap();
+  let map = &resp[0];
+  assert_eq!(map.approved, Approval::WIP);
+  assert_eq!(map.bpm, 172.5);
+  assert_eq!(map.genre_id, Genre::Electronic);
+  assert_eq!(map.language_id, Language::Instrumental);
+  assert!(matches!(map.mode, GameMode::Taiko));
+  assert!(map.approved_date.is_none());
+  assert!(map.max_combo.is_none());
+
+  // serialized in the same format as the API respond
+  let json = serde_json::to_string(&resp).unwrap();
+  let again: Vec<GetBeatmapsResp> = serde_json::from_str(&json).unwrap();
+  assert_eq!(again[0].bpm, 172.5);
+  assert_eq!(again[0].last_update, map.last_update);
+  assert_eq!(again[0].approved, Approval::WIP);
+}
@@ -0,0 +1,95 @@
+use super::{
+  de::{s_to_bool, s_to_datetime, s_to_f64, s_to_mods_flags, s_to_u32, s_to_u64},
+  GameMode, ModsFlag, UserId,
+};
+use crate::api_v1::{req::Query, Error as ReqError};
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to get_user_best API,
+read https://github.com/ppy/osu-api/wiki#apiget_user_best for meaning")]
+pub struct GetUserBestProp<'k, 'u> {
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
+  #[builder(setter(transform = |id: impl Into<UserId<'u>>| id.into()))]
+  pub(crate) user_info: UserId<'u>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Amount of results. Range between 1 and 100.
+  #[builder(default = 10)]
+  pub(crate) limit: u8,
+}
+
+impl<'k, 'u> TryFrom<GetUserBestProp<'k, 'u>> for Query {
+  type Error = ReqError;
+
+  fn try_from(prop: GetUserBestProp<'k, 'u>) -> std::result::Result<Self, Self::Error> {
+    let mut query = Self::new();
+
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
+
+    match prop.user_info {
+      UserId::Id(number) => {
+        query.push("u", number);
+      }
+      UserId::Username(name) => {
+        query.push("type", "string");
+        query.push("u", name);
+      }
+    };
+
+    if let Some(mode) = prop.mode {
+      query.push("m", mode);
+    }
+
+    if !(1..=100).contains(&prop.limit) {
+      return Err(ReqError::InvalidRequestParams);
+    }
+    query.push("limit", prop.limit);
+
+    Ok(query)
+  }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetUserBestResp {
+  #[serde(deserialize_with = "s_to_u64")]
+  pub beatmap_id: u64,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub score_id: u64,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub score: u64,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub maxcombo: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count50: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count100: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count300: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countmiss: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countkatu: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countgeki: u32,
+  // true = maximum combo of map reached, false otherwise
+  #[serde(deserialize_with = "s_to_bool")]
+  pub perfect: bool,
+  #[serde(deserialize_with = "s_to_mods_flags")]
+  pub enabled_mods: ModsFlag,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub user_id: u64,
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub date: chrono::DateTime<chrono::Utc>,
+  pub rank: String,
+  // Float value, 4 decimals
+  #[serde(deserialize_with = "s_to_f64")]
+  pub pp: f64,
+  // If the replay is available or not
+  #[serde(deserialize_with = "s_to_bool")]
+  pub replay_available: bool,
+}
@@ -1,5 +1,9 @@
-use super::{beatmaps::Approval, ModsFlag};
-use chrono::{TimeZone, Utc};
+use super::{
+  beatmaps::Approval,
+  multiplayer::{ScoringType, Team, TeamType},
+  GameMode, Genre, Language, ModsFlag,
+};
+use chrono::{NaiveDateTime, TimeZone, Utc};
 use paste::paste;
 use serde::{Deserialize, Deserializer};
 
@@ -8,9 +12,11 @@ where
   D: Deserializer<'de>,
 {
   let mods: String = Deserialize::deserialize(d)?;
-  let mods: u64 = mods
-    .parse()
-    .expect("Expect u64 but get unexpected type when parsing mods");
+  let mods: u64 = mods.parse().map_err(|err| {
+    serde::de::Error::custom(format!(
+      "Expecting u64 but found unexpecting data when parsing mods: {err}"
+    ))
+  })?;
   let flag =
     ModsFlag::from_bits(mods).ok_or_else(|| serde::de::Error::custom("invalid mods bitflag"))?;
   Ok(flag)
@@ -21,11 +27,7 @@ where
   D: Deserializer<'de>,
 {
   let is: String = Deserialize::deserialize(d)?;
-  let is = match is.as_str() {
-    "0" => false,
-    _ => true,
-  };
-  Ok(is)
+  Ok(is != "0")
 }
 
 pub(crate) fn s_to_datetime<'de, D>(d: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
@@ -33,12 +35,12 @@ where
   D: Deserializer<'de>,
 {
   let date: String = Deserialize::deserialize(d)?;
-  let date = Utc.datetime_from_str(&date, "%F %T").map_err(|err| {
+  let date = NaiveDateTime::parse_from_str(&date, "%F %T").map_err(|err| {
     serde::de::Error::custom(format!(
       "response datetime is not in expecting format: {err}"
     ))
   })?;
-  Ok(date)
+  Ok(Utc.from_utc_datetime(&date))
 }
 
 macro_rules! s_to_scalar {
@@ -61,7 +63,7 @@ macro_rules! s_to_scalar {
   }
 }
 
-s_to_scalar![u16, u32, u64, f32, f64];
+s_to_scalar![u32, u64, f32, f64];
 
 pub(crate) fn s_to_approval<'de, D>(d: D) -> Result<Approval, D::Error>
 where
@@ -85,3 +87,199 @@ where
   };
   Ok(a)
 }
+
+/// Deserialize a nullable numeric string, like the `pp_rank` of a user who
+/// never played, into `Option<T>`.
+pub(crate) fn s_to_opt<'de, D, T>(d: D) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: std::str::FromStr,
+  T::Err: std::fmt::Display,
+{
+  let s: Option<String> = Deserialize::deserialize(d)?;
+  s.map(|s| {
+    s.parse().map_err(|err| {
+      serde::de::Error::custom(format!(
+        "Expecting {} but found unexpecting data: {err}",
+        std::any::type_name::<T>()
+      ))
+    })
+  })
+  .transpose()
+}
+
+pub(crate) fn s_to_opt_datetime<'de, D>(
+  d: D,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  struct Wrapper(#[serde(deserialize_with = "s_to_datetime")] chrono::DateTime<chrono::Utc>);
+
+  let date: Option<Wrapper> = Deserialize::deserialize(d)?;
+  Ok(date.map(|Wrapper(date)| date))
+}
+
+pub(crate) fn s_to_opt_mods_flags<'de, D>(d: D) -> Result<Option<ModsFlag>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  struct Wrapper(#[serde(deserialize_with = "s_to_mods_flags")] ModsFlag);
+
+  let mods: Option<Wrapper> = Deserialize::deserialize(d)?;
+  Ok(mods.map(|Wrapper(mods)| mods))
+}
+
+pub(crate) fn s_to_game_mode<'de, D>(d: D) -> Result<GameMode, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let mode: String = Deserialize::deserialize(d)?;
+  let m = match mode.as_str() {
+    "0" => GameMode::Standard,
+    "1" => GameMode::Taiko,
+    "2" => GameMode::CatchTheBeat,
+    "3" => GameMode::Mania,
+    _ => {
+      return Err(serde::de::Error::custom(format!(
+        "Unknown game mode {}",
+        mode
+      )))
+    }
+  };
+  Ok(m)
+}
+
+pub(crate) fn s_to_scoring_type<'de, D>(d: D) -> Result<ScoringType, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let scoring: String = Deserialize::deserialize(d)?;
+  let s = match scoring.as_str() {
+    "0" => ScoringType::Score,
+    "1" => ScoringType::Accuracy,
+    "2" => ScoringType::Combo,
+    "3" => ScoringType::ScoreV2,
+    _ => {
+      return Err(serde::de::Error::custom(format!(
+        "Unknown scoring type {}",
+        scoring
+      )))
+    }
+  };
+  Ok(s)
+}
+
+pub(crate) fn s_to_team_type<'de, D>(d: D) -> Result<TeamType, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let team_type: String = Deserialize::deserialize(d)?;
+  let t = match team_type.as_str() {
+    "0" => TeamType::HeadToHead,
+    "1" => TeamType::TagCoop,
+    "2" => TeamType::TeamVs,
+    "3" => TeamType::TagTeamVs,
+    _ => {
+      return Err(serde::de::Error::custom(format!(
+        "Unknown team type {}",
+        team_type
+      )))
+    }
+  };
+  Ok(t)
+}
+
+pub(crate) fn s_to_team<'de, D>(d: D) -> Result<Team, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let team: String = Deserialize::deserialize(d)?;
+  let t = match team.as_str() {
+    "0" => Team::None,
+    "1" => Team::Blue,
+    "2" => Team::Red,
+    _ => return Err(serde::de::Error::custom(format!("Unknown team {}", team))),
+  };
+  Ok(t)
+}
+
+pub(crate) fn base64_to_bytes<'de, D>(d: D) -> Result<Vec<u8>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  use base64::Engine;
+
+  let content: String = Deserialize::deserialize(d)?;
+  base64::engine::general_purpose::STANDARD
+    .decode(content)
+    .map_err(|err| serde::de::Error::custom(format!("content is not valid base64: {err}")))
+}
+
+pub(crate) fn s_to_genre<'de, D>(d: D) -> Result<Genre, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let genre: String = Deserialize::deserialize(d)?;
+  let g = match genre.as_str() {
+    "0" => Genre::Any,
+    "1" => Genre::Unspecified,
+    "2" => Genre::VideoGame,
+    "3" => Genre::Anime,
+    "4" => Genre::Rock,
+    "5" => Genre::Pop,
+    "6" => Genre::Other,
+    "7" => Genre::Novelty,
+    "9" => Genre::HipHop,
+    "10" => Genre::Electronic,
+    "11" => Genre::Metal,
+    "12" => Genre::Classical,
+    "13" => Genre::Folk,
+    "14" => Genre::Jazz,
+    _ => return Err(serde::de::Error::custom(format!("Unknown genre {}", genre))),
+  };
+  Ok(g)
+}
+
+pub(crate) fn s_to_language<'de, D>(d: D) -> Result<Language, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let language: String = Deserialize::deserialize(d)?;
+  let l = match language.as_str() {
+    "0" => Language::Any,
+    "1" => Language::Unspecified,
+    "2" => Language::English,
+    "3" => Language::Japanese,
+    "4" => Language::Chinese,
+    "5" => Language::Instrumental,
+    "6" => Language::Korean,
+    "7" => Language::French,
+    "8" => Language::German,
+    "9" => Language::Swedish,
+    "10" => Language::Spanish,
+    "11" => Language::Italian,
+    "12" => Language::Russian,
+    "13" => Language::Polish,
+    "14" => Language::Other,
+    _ => {
+      return Err(serde::de::Error::custom(format!(
+        "Unknown language {}",
+        language
+      )))
+    }
+  };
+  Ok(l)
+}
+
+#[test]
+fn test_invalid_mods() {
+  #[derive(Deserialize, Debug)]
+  struct Mods(#[serde(deserialize_with = "s_to_mods_flags")] ModsFlag);
+
+  let mods: Mods = serde_json::from_str(r#""72""#).unwrap();
+  assert_eq!(mods.0, ModsFlag::HIDDEN | ModsFlag::DOUBLETIME);
+  assert!(serde_json::from_str::<Mods>(r#""HDDT""#).is_err());
+}
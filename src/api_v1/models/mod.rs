@@ -1,116 +1,26 @@
 mod beatmaps;
+mod best;
 mod de;
+mod multiplayer;
 mod recent;
-
-pub use beatmaps::{GetBeatmapsProps, GetBeatmapsResp};
+mod replay;
+mod scores;
+mod ser;
+mod user;
+
+pub use beatmaps::{Approval, GetBeatmapsProps, GetBeatmapsResp};
+pub use best::{GetUserBestProp, GetUserBestResp};
+pub use multiplayer::{
+  GetMatchProp, GetMatchResp, MatchGame, MatchInfo, MatchScore, ScoringType, Team, TeamType,
+};
 pub use recent::{GetUserRecentProp, GetUserRecentResp};
+pub use replay::{GetReplayProp, GetReplayResp};
+pub use scores::{GetScoresProp, GetScoresResp};
+pub use user::{GetUserProp, GetUserResp, UserEvent};
 
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub enum GameMode {
-  Standard,
-  Taiko,
-  CatchTheBeat,
-  Mania,
-}
-
-impl std::fmt::Display for GameMode {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", self.as_ref())
-  }
-}
-
-impl AsRef<str> for GameMode {
-  fn as_ref(&self) -> &str {
-    use GameMode::*;
-
-    match self {
-      Standard => "0",
-      Taiko => "1",
-      CatchTheBeat => "2",
-      Mania => "3",
-    }
-  }
-}
-
-#[derive(Debug)]
-pub enum UserId<'u> {
-  Id(u64),
-  Username(&'u str),
-}
-
-impl From<u64> for UserId<'_> {
-  fn from(id: u64) -> Self {
-    Self::Id(id)
-  }
-}
-
-impl<'a> From<&'a str> for UserId<'a> {
-  fn from(name: &'a str) -> Self {
-    Self::Username(name)
-  }
-}
+pub use crate::common::{GameMode, Genre, Language, ModsFlag, UserId};
 
-bitflags::bitflags! {
-  pub struct ModsFlag: u64 {
-    const NONE              = 0;
-    const NOFAIL            = 1;
-    const EASY              = 2;
-    const TOUCHDEVICE       = 4;
-    const HIDDEN            = 8;
-    const HARDROCK          = 16;
-    const SUDDENDEATH       = 32;
-    const DOUBLETIME        = 64;
-    const RELAX             = 128;
-    const HALFTIME          = 256;
-    const NIGHTCORE         = 512 | Self::DOUBLETIME.bits;
-    const FLASHLIGHT        = 1024;
-    const AUTOPLAY          = 2048;
-    const SPUNOUT           = 4096;
-    const RELAX2            = 8192;    // Autopilot
-    const PERFECT           = 16384 | Self::SUDDENDEATH.bits;
-    const KEY4              = 32768;
-    const KEY5              = 65536;
-    const KEY6              = 131072;
-    const KEY7              = 262144;
-    const KEY8              = 524288;
-    const FADEIN            = 1048576;
-    const RANDOM            = 2097152;
-    const CINEMA            = 4194304;
-    const TARGET            = 8388608;
-    const KEY9              = 16777216;
-    const KEYCOOP           = 33554432;
-    const KEY1              = 67108864;
-    const KEY3              = 134217728;
-    const KEY2              = 268435456;
-    const SCOREV2           = 536870912;
-    const MIRROR            = 1073741824;
-    const KEYMOD            = Self::KEY1.bits
-                            | Self::KEY2.bits
-                            | Self::KEY3.bits
-                            | Self::KEY4.bits
-                            | Self::KEY5.bits
-                            | Self::KEY6.bits
-                            | Self::KEY7.bits
-                            | Self::KEY8.bits
-                            | Self::KEY9.bits
-                            | Self::KEYCOOP.bits;
-    const FREEMODALLOWED    = Self::NOFAIL.bits
-                            | Self::EASY.bits
-                            | Self::HIDDEN.bits
-                            | Self::HARDROCK.bits
-                            | Self::SUDDENDEATH.bits
-                            | Self::FLASHLIGHT.bits
-                            | Self::FADEIN.bits
-                            | Self::RELAX.bits
-                            | Self::RELAX2.bits
-                            | Self::SPUNOUT.bits
-                            | Self::KEYMOD.bits;
-    const SCOREINCREASEMODS = Self::HIDDEN.bits
-                            | Self::HARDROCK.bits
-                            | Self::DOUBLETIME.bits
-                            | Self::FLASHLIGHT.bits
-                            | Self::FADEIN.bits;
-  }
+/// Combine a list of mods into the single bitwise value expected by the `mods` param
+pub(crate) fn fold_mods(mods: &[ModsFlag]) -> u64 {
+  mods.iter().fold(0_u64, |accum, item| accum | item.bits())
 }
@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+use typed_builder::TypedBuilder;
+
+use super::{
+  de::{
+    s_to_bool, s_to_datetime, s_to_game_mode, s_to_mods_flags, s_to_opt_datetime,
+    s_to_opt_mods_flags, s_to_scoring_type, s_to_team, s_to_team_type, s_to_u32, s_to_u64,
+  },
+  GameMode, ModsFlag,
+};
+use crate::api_v1::{req::Query, Error as ReqError};
+
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to get_match API,
+read https://github.com/ppy/osu-api/wiki#apiget_match for meaning")]
+pub struct GetMatchProp<'k> {
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
+  pub(crate) match_id: u64,
+}
+
+impl<'k> TryFrom<GetMatchProp<'k>> for Query {
+  type Error = ReqError;
+
+  fn try_from(prop: GetMatchProp<'k>) -> std::result::Result<Self, Self::Error> {
+    let mut query = Self::new();
+
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
+    query.push("mp", prop.match_id);
+
+    Ok(query)
+  }
+}
+
+#[derive(Debug)]
+pub enum ScoringType {
+  Score = 0,
+  Accuracy = 1,
+  Combo = 2,
+  ScoreV2 = 3,
+}
+
+#[derive(Debug)]
+pub enum TeamType {
+  HeadToHead = 0,
+  TagCoop = 1,
+  TeamVs = 2,
+  TagTeamVs = 3,
+}
+
+#[derive(Debug)]
+pub enum Team {
+  None = 0,
+  Blue = 1,
+  Red = 2,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetMatchResp {
+  // The API respond `"match": 0` when the given match id doesn't exist
+  #[serde(rename = "match", deserialize_with = "match_or_zero")]
+  pub match_info: Option<MatchInfo>,
+  pub games: Vec<MatchGame>,
+}
+
+fn match_or_zero<'de, D>(d: D) -> Result<Option<MatchInfo>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum MaybeMatch {
+    Found(MatchInfo),
+    NotFound(u8),
+  }
+
+  let info = match MaybeMatch::deserialize(d)? {
+    MaybeMatch::Found(info) => Some(info),
+    MaybeMatch::NotFound(0) => None,
+    MaybeMatch::NotFound(n) => {
+      return Err(serde::de::Error::custom(format!(
+        "Unexpected match placeholder {n}"
+      )))
+    }
+  };
+  Ok(info)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MatchInfo {
+  #[serde(deserialize_with = "s_to_u64")]
+  pub match_id: u64,
+  pub name: String,
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub start_time: DateTime<Utc>,
+  // null if the match is not disbanded yet
+  #[serde(default, deserialize_with = "s_to_opt_datetime")]
+  pub end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MatchGame {
+  #[serde(deserialize_with = "s_to_u64")]
+  pub game_id: u64,
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub start_time: DateTime<Utc>,
+  // null if the game is not finished yet
+  #[serde(default, deserialize_with = "s_to_opt_datetime")]
+  pub end_time: Option<DateTime<Utc>>,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub beatmap_id: u64,
+  #[serde(deserialize_with = "s_to_game_mode")]
+  pub play_mode: GameMode,
+  // winning condition: 0 = score, 1 = accuracy, 2 = combo, 3 = score v2
+  #[serde(deserialize_with = "s_to_scoring_type")]
+  pub scoring_type: ScoringType,
+  // 0 = head to head, 1 = tag co-op, 2 = team vs, 3 = tag team vs
+  #[serde(deserialize_with = "s_to_team_type")]
+  pub team_type: TeamType,
+  // global mods of the game
+  #[serde(deserialize_with = "s_to_mods_flags")]
+  pub mods: ModsFlag,
+  pub scores: Vec<MatchScore>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MatchScore {
+  // 0 based index of player's slot
+  #[serde(deserialize_with = "s_to_u32")]
+  pub slot: u32,
+  // 0 = no team, 1 = blue, 2 = red
+  #[serde(deserialize_with = "s_to_team")]
+  pub team: Team,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub user_id: u64,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub score: u64,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub maxcombo: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count50: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count100: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count300: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countmiss: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countgeki: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countkatu: u32,
+  // full combo or not
+  #[serde(deserialize_with = "s_to_bool")]
+  pub perfect: bool,
+  // if the player failed at the end of the map
+  #[serde(deserialize_with = "s_to_bool")]
+  pub pass: bool,
+  // mods enabled by player, only present when free mod is enabled
+  #[serde(default, deserialize_with = "s_to_opt_mods_flags")]
+  pub enabled_mods: Option<ModsFlag>,
+}
+
+#[test]
+fn test_deserialize_match() {
+  let resp = r#"{"match": 0, "games": []}"#;
+  let resp: GetMatchResp = serde_json::from_str(resp).unwrap();
+  assert!(resp.match_info.is_none());
+
+  let resp = r#"{
+    "match": {
+      "match_id": "59185623", "name": "OWC2019: (Japan) vs (South Korea)",
+      "start_time": "2019-12-14 10:03:44", "end_time": null
+    },
+    "games": [{
+      "game_id": "306236436", "start_time": "2019-12-14 10:14:58",
+      "end_time": "2019-12-14 10:18:36", "beatmap_id": "2247470", "play_mode": "0",
+      "match_type": "0", "scoring_type": "3", "team_type": "2", "mods": "1",
+      "scores": [{
+        "slot": "0", "team": "1", "user_id": "4787150", "score": "912345",
+        "maxcombo": "824", "rank": "0", "count50": "0", "count100": "12",
+        "count300": "610", "countmiss": "1", "countgeki": "120", "countkatu": "9",
+        "perfect": "0", "pass": "1", "enabled_mods": null
+      }]
+    }]
+  }"#;
+  let resp: GetMatchResp = serde_json::from_str(resp).unwrap();
+  assert_eq!(resp.match_info.unwrap().match_id, 59185623);
+  assert!(matches!(resp.games[0].team_type, TeamType::TeamVs));
+  assert!(matches!(resp.games[0].scores[0].team, Team::Blue));
+  assert!(resp.games[0].scores[0].pass);
+}
@@ -8,13 +8,14 @@ use typed_builder::TypedBuilder;
 
 #[derive(TypedBuilder, Debug)]
 pub struct GetUserRecentProp<'k, 'u> {
-  api_key: &'k str,
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
   #[builder(setter(transform = |id: impl Into<UserId<'u>>| id.into()))]
-  user_info: UserId<'u>,
+  pub(crate) user_info: UserId<'u>,
   #[builder(default, setter(strip_option))]
-  mode: Option<GameMode>,
+  pub(crate) mode: Option<GameMode>,
   #[builder(default = 10)]
-  limit: u8,
+  pub(crate) limit: u8,
 }
 
 impl<'k, 'u> TryFrom<GetUserRecentProp<'k, 'u>> for Query {
@@ -23,7 +24,9 @@ impl<'k, 'u> TryFrom<GetUserRecentProp<'k, 'u>> for Query {
   fn try_from(prop: GetUserRecentProp<'k, 'u>) -> std::result::Result<Self, Self::Error> {
     let mut query = Self::new();
 
-    query.push("k", prop.api_key);
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
 
     match prop.user_info {
       UserId::Id(number) => {
@@ -0,0 +1,83 @@
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+use super::{de::base64_to_bytes, fold_mods, GameMode, ModsFlag, UserId};
+use crate::api_v1::{req::Query, Error as ReqError};
+
+/// Request to get_replay API. A replay can be located either by `score_id`, or by the
+/// combination of `beatmap_id`, `user_info` and `mode`.
+///
+/// Notes that this API is limited to 10 requests per minute.
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to get_replay API,
+read https://github.com/ppy/osu-api/wiki#apiget_replay for meaning")]
+pub struct GetReplayProp<'k, 'u> {
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) score_id: Option<u64>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) beatmap_id: Option<u64>,
+  #[builder(default, setter(transform = |id: impl Into<UserId<'u>>| Some(id.into())))]
+  pub(crate) user_info: Option<UserId<'u>>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  #[builder(default = Vec::new())]
+  pub(crate) mods: Vec<ModsFlag>,
+}
+
+impl<'k, 'u> TryFrom<GetReplayProp<'k, 'u>> for Query {
+  type Error = ReqError;
+
+  fn try_from(prop: GetReplayProp<'k, 'u>) -> std::result::Result<Self, Self::Error> {
+    let mut query = Self::new();
+
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
+
+    if let Some(score_id) = prop.score_id {
+      query.push("s", score_id);
+    } else {
+      let (Some(beatmap_id), Some(user_info), Some(mode)) =
+        (prop.beatmap_id, prop.user_info, prop.mode)
+      else {
+        return Err(ReqError::InvalidRequestParams);
+      };
+
+      query.push("b", beatmap_id);
+      match user_info {
+        UserId::Id(number) => {
+          query.push("u", number);
+        }
+        UserId::Username(name) => {
+          query.push("type", "string");
+          query.push("u", name);
+        }
+      };
+      query.push("m", mode);
+    }
+
+    if !prop.mods.is_empty() {
+      query.push("mods", fold_mods(&prop.mods));
+    }
+
+    Ok(query)
+  }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetReplayResp {
+  // The LZMA compressed replay frames, already decoded from base64. It is the
+  // same data as the compressed data section of an osr file.
+  #[serde(deserialize_with = "base64_to_bytes")]
+  pub content: Vec<u8>,
+  pub encoding: String,
+}
+
+#[test]
+fn test_deserialize_replay() {
+  let replay: GetReplayResp =
+    serde_json::from_str(r#"{"content": "XQAAIAA=", "encoding": "base64"}"#).unwrap();
+  assert_eq!(replay.content, [0x5d, 0x00, 0x00, 0x20, 0x00]);
+}
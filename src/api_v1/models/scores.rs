@@ -0,0 +1,105 @@
+use super::{
+  de::{s_to_bool, s_to_datetime, s_to_mods_flags, s_to_opt, s_to_u32, s_to_u64},
+  fold_mods, GameMode, ModsFlag, UserId,
+};
+use crate::api_v1::{req::Query, Error as ReqError};
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to get_scores API,
+read https://github.com/ppy/osu-api/wiki#apiget_scores for meaning")]
+pub struct GetScoresProp<'k, 'u> {
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
+  pub(crate) beatmap_id: u64,
+  #[builder(default, setter(transform = |id: impl Into<UserId<'u>>| Some(id.into())))]
+  pub(crate) user_info: Option<UserId<'u>>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Only return scores played with exactly the given mods combination
+  #[builder(default = Vec::new())]
+  pub(crate) mods: Vec<ModsFlag>,
+  /// Amount of results. Range between 1 and 100.
+  #[builder(default = 50)]
+  pub(crate) limit: u8,
+}
+
+impl<'k, 'u> TryFrom<GetScoresProp<'k, 'u>> for Query {
+  type Error = ReqError;
+
+  fn try_from(prop: GetScoresProp<'k, 'u>) -> std::result::Result<Self, Self::Error> {
+    let mut query = Self::new();
+
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
+    query.push("b", prop.beatmap_id);
+
+    if let Some(user_info) = prop.user_info {
+      match user_info {
+        UserId::Id(number) => {
+          query.push("u", number);
+        }
+        UserId::Username(name) => {
+          query.push("type", "string");
+          query.push("u", name);
+        }
+      };
+    }
+
+    if let Some(mode) = prop.mode {
+      query.push("m", mode);
+    }
+
+    if !prop.mods.is_empty() {
+      query.push("mods", fold_mods(&prop.mods));
+    }
+
+    if !(1..=100).contains(&prop.limit) {
+      return Err(ReqError::InvalidRequestParams);
+    }
+    query.push("limit", prop.limit);
+
+    Ok(query)
+  }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetScoresResp {
+  #[serde(deserialize_with = "s_to_u64")]
+  pub score_id: u64,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub score: u64,
+  pub username: String,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count300: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count100: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub count50: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countmiss: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub maxcombo: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countkatu: u32,
+  #[serde(deserialize_with = "s_to_u32")]
+  pub countgeki: u32,
+  // true = maximum combo of map reached, false otherwise
+  #[serde(deserialize_with = "s_to_bool")]
+  pub perfect: bool,
+  #[serde(deserialize_with = "s_to_mods_flags")]
+  pub enabled_mods: ModsFlag,
+  #[serde(deserialize_with = "s_to_u64")]
+  pub user_id: u64,
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub date: chrono::DateTime<chrono::Utc>,
+  pub rank: String,
+  // Float value, 4 decimals. Null for beatmap that doesn't give pp, like loved map.
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub pp: Option<f64>,
+  // If the replay is available or not
+  #[serde(deserialize_with = "s_to_bool")]
+  pub replay_available: bool,
+}
@@ -0,0 +1,49 @@
+//! Serialize fields back into the string format the API respond with, so that a serialized
+//! response can be deserialized again by the functions in `de.rs`.
+
+use chrono::{DateTime, Utc};
+use serde::Serializer;
+use std::fmt::Display;
+
+pub(crate) fn to_s<T, S>(v: &T, s: S) -> Result<S::Ok, S::Error>
+where
+  T: Display,
+  S: Serializer,
+{
+  s.collect_str(v)
+}
+
+pub(crate) fn opt_to_s<T, S>(v: &Option<T>, s: S) -> Result<S::Ok, S::Error>
+where
+  T: Display,
+  S: Serializer,
+{
+  match v {
+    Some(v) => s.collect_str(v),
+    None => s.serialize_none(),
+  }
+}
+
+pub(crate) fn bool_to_s<S>(v: &bool, s: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  s.serialize_str(if *v { "1" } else { "0" })
+}
+
+pub(crate) fn datetime_to_s<S>(v: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  s.collect_str(&v.format("%F %T"))
+}
+
+pub(crate) fn opt_datetime_to_s<S>(v: &Option<DateTime<Utc>>, s: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  match v {
+    Some(v) => datetime_to_s(v, s),
+    None => s.serialize_none(),
+  }
+}
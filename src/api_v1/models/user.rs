@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+use super::{
+  de::{s_to_datetime, s_to_opt, s_to_u32, s_to_u64},
+  GameMode, UserId,
+};
+use crate::api_v1::{req::Query, Error as ReqError};
+
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to get_user API,
+read https://github.com/ppy/osu-api/wiki#apiget_user for meaning")]
+pub struct GetUserProp<'k, 'u> {
+  #[builder(default, setter(strip_option))]
+  pub(crate) api_key: Option<&'k str>,
+  #[builder(setter(transform = |id: impl Into<UserId<'u>>| id.into()))]
+  pub(crate) user_info: UserId<'u>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Max number of days between now and last event date. Range of 1-31.
+  #[builder(default, setter(strip_option))]
+  pub(crate) event_days: Option<u8>,
+}
+
+impl<'k, 'u> TryFrom<GetUserProp<'k, 'u>> for Query {
+  type Error = ReqError;
+
+  fn try_from(prop: GetUserProp<'k, 'u>) -> std::result::Result<Self, Self::Error> {
+    let mut query = Self::new();
+
+    if let Some(key) = prop.api_key {
+      query.push("k", key);
+    }
+
+    match prop.user_info {
+      UserId::Id(number) => {
+        query.push("u", number);
+      }
+      UserId::Username(name) => {
+        query.push("type", "string");
+        query.push("u", name);
+      }
+    };
+
+    if let Some(mode) = prop.mode {
+      query.push("m", mode);
+    }
+
+    if let Some(days) = prop.event_days {
+      if !(1..=31).contains(&days) {
+        return Err(ReqError::InvalidRequestParams);
+      }
+      query.push("event_days", days);
+    }
+
+    Ok(query)
+  }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetUserResp {
+  #[serde(deserialize_with = "s_to_u64")]
+  pub user_id: u64,
+  pub username: String,
+  // date the account was registered, in UTC
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub join_date: DateTime<Utc>,
+  // Total amount for all ranked, approved, and loved beatmaps played.
+  // All of the statistic below are null for user who never played.
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count300: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count100: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count50: Option<u64>,
+  // Only counts ranked, approved, and loved beatmaps
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub playcount: Option<u64>,
+  // Counts the best individual score on each ranked, approved, and loved beatmaps
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub ranked_score: Option<u64>,
+  // Counts every score on ranked, approved, and loved beatmaps
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub total_score: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub pp_rank: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub level: Option<f64>,
+  // For inactive players this will be 0 to purge them from leaderboards
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub pp_raw: Option<f64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub accuracy: Option<f64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count_rank_ss: Option<u64>,
+  // silver SS count
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count_rank_ssh: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count_rank_s: Option<u64>,
+  // silver S count
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count_rank_sh: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub count_rank_a: Option<u64>,
+  // Uses the ISO3166-1 alpha-2 country code naming
+  pub country: String,
+  // Cumulative time played
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub total_seconds_played: Option<u64>,
+  // The user's rank in the country.
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub pp_country_rank: Option<u64>,
+  // Contains events for this user
+  pub events: Vec<UserEvent>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UserEvent {
+  pub display_html: String,
+  // beatmap and beatmapset are null for event like supporter gifting
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub beatmap_id: Option<u64>,
+  #[serde(default, deserialize_with = "s_to_opt")]
+  pub beatmapset_id: Option<u64>,
+  #[serde(deserialize_with = "s_to_datetime")]
+  pub date: DateTime<Utc>,
+  // How "epic" this event is (between 1 and 32)
+  #[serde(deserialize_with = "s_to_u32")]
+  pub epicfactor: u32,
+}
+
+#[test]
+fn test_deserialize_inactive_user() {
+  let resp = r#"[{
+    "user_id": "2", "username": "peppy", "join_date": "2007-08-28 03:09:12",
+    "count300": null, "count100": null, "count50": null, "playcount": null,
+    "ranked_score": null, "total_score": null, "pp_rank": null, "level": null,
+    "pp_raw": null, "accuracy": null, "count_rank_ss": null, "count_rank_ssh": null,
+    "count_rank_s": null, "count_rank_sh": null, "count_rank_a": null, "country": "AU",
+    "total_seconds_played": null, "pp_country_rank": null,
+    "events": [{
+      "display_html": "<b>peppy</b> has once again chosen to support osu!",
+      "beatmap_id": null, "beatmapset_id": null, "date": "2022-11-28 02:30:50",
+      "epicfactor": "2"
+    }]
+  }]"#;
+  let resp: Vec<GetUserResp> = serde_json::from_str(resp).unwrap();
+  assert_eq!(resp[0].user_id, 2);
+  assert!(resp[0].pp_raw.is_none());
+  assert!(resp[0].events[0].beatmap_id.is_none());
+}
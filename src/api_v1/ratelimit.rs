@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use crate::{
+  api_v1::{api::with_endpoints, Error, OsuApiRequester, Query},
+  ratelimit::RateLimiter,
+};
+
+/// Wrap an [`OsuApiRequester`] to wait for the [`RateLimiter`] before every request.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), osu_api::api_v1::Error> {
+/// use osu_api::{
+///   api_v1::{GetBeatmapsProps, OsuApiRequester, OsuClient, RateLimited},
+///   ratelimit::RateLimiter,
+/// };
+///
+/// let client = RateLimited::new(OsuClient::new("YOUR_API_KEY"), RateLimiter::per_minute(60));
+/// let props = GetBeatmapsProps::builder().beatmapset_id(896080).build();
+/// let maps = client.get_beatmaps(props).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The limiter can be shared by multiple clients with [`RateLimited::with_shared`].
+#[derive(Debug, Clone)]
+pub struct RateLimited<R> {
+  inner: R,
+  limiter: Arc<RateLimiter>,
+}
+
+impl<R> RateLimited<R> {
+  pub fn new(inner: R, limiter: RateLimiter) -> Self {
+    Self::with_shared(inner, Arc::new(limiter))
+  }
+
+  pub fn with_shared(inner: R, limiter: Arc<RateLimiter>) -> Self {
+    Self { inner, limiter }
+  }
+
+  /// Get the wrapped requester. Notes that requests sent by it directly are not limited.
+  pub fn inner(&self) -> &R {
+    &self.inner
+  }
+
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+}
+
+macro_rules! impl_rate_limited {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    #[async_trait::async_trait]
+    impl<R> OsuApiRequester for RateLimited<R>
+    where
+      R: OsuApiRequester + Send + Sync,
+    {
+      $(
+        async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+        where Q: TryInto<Query, Error = Error> + Send + Sync
+        {
+          self.limiter.acquire().await;
+          self.inner.$name(query).await
+        }
+      )+
+    }
+  };
+}
+
+with_endpoints!(impl_rate_limited);
@@ -1,99 +1,177 @@
-use std::fmt::Display;
+use std::time::Duration;
 
-use crate::api_v1::{ApiEndpoint, Error, GetBeatmapsResp, GetUserRecentResp, OsuApiRequester};
+use paste::paste;
+use serde::de::DeserializeOwned;
 
-pub struct Query {
-  pair: Vec<String>,
+use crate::api_v1::{api::with_endpoints, ApiEndpoint, Error, OsuApiRequester};
+
+pub use crate::common::Query;
+use crate::common::{error_message, retry_after};
+
+// Allow passing an already converted Query to the requester, like the decorators re-sending it
+impl TryFrom<&Query> for Query {
+  type Error = Error;
+
+  fn try_from(query: &Query) -> Result<Self, Self::Error> {
+    Ok(query.clone())
+  }
 }
 
-impl Query {
-  pub fn new() -> Self {
-    Self { pair: Vec::new() }
+/// Check the response status and body, and convert API error into [`Error`]
+fn check_response(
+  status: reqwest::StatusCode,
+  retry_after: Option<Duration>,
+  body: &[u8],
+) -> Result<(), Error> {
+  use reqwest::StatusCode;
+
+  // Server errors are transient whatever the body says, so they can be retried
+  if status.is_server_error() {
+    return Err(Error::ServerError(status));
   }
 
-  pub fn push(&mut self, key: impl Display, val: impl Display) {
-    self.pair.push(format!("{key}={val}"))
+  // Error object like `{"error": "Please provide a valid API key."}`
+  if let Some(error) = error_message(body) {
+    let err = if status == StatusCode::UNAUTHORIZED || error.contains("API key") {
+      Error::InvalidApiKey
+    } else if status == StatusCode::TOO_MANY_REQUESTS || error.contains("too fast") {
+      Error::RateLimited { retry_after }
+    } else if error.contains("Replay not available") {
+      Error::ReplayUnavailable(error)
+    } else {
+      Error::Api(error)
+    };
+    return Err(err);
   }
 
-  pub fn into_query_str(self) -> String {
-    self.pair.into_iter().fold(String::new(), |accum, item| {
-      if accum.is_empty() {
-        item
-      } else {
-        format!("{accum}&{item}")
-      }
-    })
+  match status {
+    StatusCode::UNAUTHORIZED => Err(Error::InvalidApiKey),
+    StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited { retry_after }),
+    status if !status.is_success() => Err(Error::ServerError(status)),
+    _ => Ok(()),
+  }
+}
+
+/// Status and body of a response, before being checked and deserialized
+pub(crate) struct RawResponse {
+  pub(crate) status: reqwest::StatusCode,
+  pub(crate) retry_after: Option<Duration>,
+  pub(crate) body: Vec<u8>,
+}
+
+impl RawResponse {
+  /// Convert API error into [`Error`], or deserialize the body
+  pub(crate) fn parse<T: DeserializeOwned>(&self) -> Result<T, Error> {
+    check_response(self.status, self.retry_after, &self.body)?;
+    let ret: T = serde_json::from_slice(&self.body)?;
+
+    Ok(ret)
   }
 }
 
+/// Send request with the given query to the endpoint URL and read the whole response
+pub(crate) async fn fetch(
+  client: &reqwest::Client,
+  mut url: reqwest::Url,
+  query: Query,
+) -> Result<RawResponse, Error> {
+  url.set_query(Some(&query.into_query_str()));
+  let resp = client.get(url).send().await?;
+  let status = resp.status();
+  let retry_after = retry_after(resp.headers());
+  let body = resp.bytes().await?.to_vec();
+
+  Ok(RawResponse {
+    status,
+    retry_after,
+    body,
+  })
+}
+
+/// Send request with the given query to the endpoint URL and deserialize the response body
+pub(crate) async fn send_request<T>(
+  client: &reqwest::Client,
+  url: reqwest::Url,
+  query: Query,
+) -> Result<T, Error>
+where
+  T: DeserializeOwned,
+{
+  fetch(client, url, query).await?.parse()
+}
+
 macro_rules! impl_reqwest {
-  (
-    $(
-      $name:ident {
-        @endpoint: $endpoint:expr;
-        @ret:  $ret:ty;
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    paste! {
+      #[async_trait::async_trait]
+      impl OsuApiRequester for reqwest::Client {
+        $(
+          async fn $name<Q>(
+            &self,
+            query: Q,
+          ) -> Result<$ret, Error>
+          where Q: TryInto<Query, Error = Error> + Send + Sync
+          {
+            let query: Query = query.try_into()?;
+            let base = reqwest::Url::parse(ApiEndpoint::DEFAULT_BASE_URL)?;
+            let url = ApiEndpoint::[<$name:camel>].url(&base)?;
+            send_request(self, url, query).await
+          }
+        )+
       }
-    )+
-  ) => {
-    #[async_trait::async_trait]
-    impl OsuApiRequester for reqwest::Client {
-      $(
-        async fn $name<Q>(
-          &self,
-          query: Q,
-        ) -> Result<$ret, Error>
-        where Q: TryInto<Query, Error = Error> + Send + Sync
-      {
-          let query: Result<Query, Error> = query.try_into();
-          let url = reqwest::Url::parse(&format!("{}?{}", $endpoint, query.unwrap().into_query_str()))
-            .expect(concat!("fail to parse param in ", stringify!($name)));
-          let resp = self.get(url).send().await?.bytes().await?;
-          let ret: $ret = serde_json::from_slice(&resp)?;
-
-          Ok(ret)
-        }
-      )+
     }
   };
 }
 
-impl_reqwest! {
-  get_user_recent {
-    @endpoint: ApiEndpoint::GET_USER_RECENT;
-    @ret: Vec<GetUserRecentResp>;
-  }
+with_endpoints!(impl_reqwest);
 
-  get_beatmaps {
-    @endpoint: ApiEndpoint::GET_BEATMAPS;
-    @ret: Vec<GetBeatmapsResp>;
-  }
+/// Requester for the tests against the real API. Responses are replayed from
+/// `tests/cassettes`, set `OSU_API_RECORD` along with `OSU_API_KEY` to record them. The tests
+/// are ignored until the cassettes are recorded.
+#[cfg(test)]
+fn cassette() -> (super::Cassette, String) {
+  use super::{Cassette, CassetteMode};
+  dotenvy::dotenv().ok();
+
+  let (mode, api_key) = if std::env::var("OSU_API_RECORD").is_ok() {
+    let api_key = std::env::var("OSU_API_KEY").expect("Require env `OSU_API_KEY` set");
+    (CassetteMode::Record, api_key)
+  } else {
+    (CassetteMode::Replay, String::new())
+  };
+  let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cassettes");
+  assert!(
+    mode == CassetteMode::Record || std::path::Path::new(dir).is_dir(),
+    "no cassette recorded, record them with \
+    `OSU_API_RECORD=1 OSU_API_KEY=<key> cargo test -- --ignored`"
+  );
+  let cassette = Cassette::builder().dir(dir).mode(mode).build();
+
+  (cassette, api_key)
 }
 
 #[tokio::test]
+#[ignore = "requires recorded cassettes"]
 async fn test_get_user_recent() {
-  use crate::api::GetUserRecentProp;
-  dotenvy::dotenv().ok();
-
-  let api_key = std::env::var("OSU_API_KEY").expect("Require env `OSU_API_KEY` set");
+  use crate::api_v1::GetUserRecentProp;
 
+  let (client, api_key) = cassette();
   let props = GetUserRecentProp::builder()
     .api_key(&api_key)
     .user_info("BlackDog5")
     .limit(1)
     .build();
-  let client = reqwest::Client::new();
   let resp = client.get_user_recent(props).await.unwrap();
 
   assert!(!resp.is_empty())
 }
 
 #[tokio::test]
+#[ignore = "requires recorded cassettes"]
 async fn test_get_beatmaps() {
-  use crate::api::GetBeatmapsProps;
-  dotenvy::dotenv().ok();
+  use crate::api_v1::GetBeatmapsProps;
 
-  let api_key = std::env::var("OSU_API_KEY").expect("Require env `OSU_API_KEY` set");
-  let client = reqwest::Client::new();
+  let (client, api_key) = cassette();
 
   // #1 Test get beatmap set
   let props = GetBeatmapsProps::builder()
@@ -127,3 +205,78 @@ async fn test_get_beatmaps() {
   let map = &resp[0];
   assert!(map.difficultyrating > 6.01);
 }
+
+#[test]
+fn test_check_response() {
+  use reqwest::StatusCode;
+
+  let body = br#"{"error": "Please provide a valid API key."}"#;
+  assert!(matches!(
+    check_response(StatusCode::UNAUTHORIZED, None, body),
+    Err(Error::InvalidApiKey)
+  ));
+
+  let body = br#"{"error": "Replay not available."}"#;
+  assert!(matches!(
+    check_response(StatusCode::OK, None, body),
+    Err(Error::ReplayUnavailable(_))
+  ));
+
+  let body = br#"{"error": "Requesting too fast! Slow your operation, cap'n!"}"#;
+  assert!(matches!(
+    check_response(StatusCode::OK, None, body),
+    Err(Error::RateLimited { retry_after: None })
+  ));
+
+  assert!(matches!(
+    check_response(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(3)), b""),
+    Err(Error::RateLimited { retry_after: Some(after) }) if after == Duration::from_secs(3)
+  ));
+
+  assert!(matches!(
+    check_response(StatusCode::BAD_GATEWAY, None, b"<html>Bad Gateway</html>"),
+    Err(Error::ServerError(StatusCode::BAD_GATEWAY))
+  ));
+
+  let body = br#"{"error": "Internal server error"}"#;
+  let err = check_response(StatusCode::INTERNAL_SERVER_ERROR, None, body).unwrap_err();
+  assert!(matches!(
+    err,
+    Error::ServerError(StatusCode::INTERNAL_SERVER_ERROR)
+  ));
+  assert!(err.is_transient());
+
+  assert!(check_response(StatusCode::OK, None, b"[]").is_ok());
+}
+
+#[tokio::test]
+async fn test_invalid_query() {
+  use crate::api_v1::GetBeatmapsProps;
+
+  // neither beatmapset id nor beatmap id is given
+  let props = GetBeatmapsProps::builder().api_key("").build();
+  let client = reqwest::Client::new();
+  let resp = client.get_beatmaps(props).await;
+  assert!(matches!(resp, Err(Error::InvalidRequestParams)));
+}
+
+#[test]
+fn test_query_encoding() {
+  let mut query = Query::new();
+  query.push("type", "string");
+  query.push("u", "Mr. Beast & Co+");
+  query.push("u", "夏目");
+
+  assert_eq!(
+    query.pairs(),
+    [
+      ("type".to_string(), "string".to_string()),
+      ("u".to_string(), "Mr. Beast & Co+".to_string()),
+      ("u".to_string(), "夏目".to_string()),
+    ]
+  );
+  assert_eq!(
+    query.into_query_str(),
+    "type=string&u=Mr.+Beast+%26+Co%2B&u=%E5%A4%8F%E7%9B%AE"
+  );
+}
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use rand::Rng;
+use typed_builder::TypedBuilder;
+
+use crate::api_v1::{api::with_endpoints, Error, OsuApiRequester, Query};
+
+/// Decide how many times and how long to wait before retrying a failed request.
+///
+/// Only transient errors are retried, see [`Error::is_transient`]. The delay doubles after every
+/// attempt, start from `base_delay` and capped by `max_delay`. If the server respond with
+/// `Retry-After` header, the given delay is used instead.
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct RetryPolicy {
+  /// Max number of attempts, including the first request
+  #[builder(default = 3)]
+  max_attempts: u32,
+  #[builder(default = Duration::from_millis(500))]
+  base_delay: Duration,
+  #[builder(default = Duration::from_secs(30))]
+  max_delay: Duration,
+  /// Randomize the delay between half and the full of the backoff, to avoid clients retrying
+  /// at the same time.
+  #[builder(default = true)]
+  jitter: bool,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self::builder().build()
+  }
+}
+
+impl RetryPolicy {
+  /// Delay before retrying the request failed with `err` at the given attempt, which starts
+  /// from 1.
+  pub fn delay(&self, attempt: u32, err: &Error) -> Duration {
+    if let Error::RateLimited {
+      retry_after: Some(after),
+    } = err
+    {
+      return *after;
+    }
+
+    let backoff = self
+      .base_delay
+      .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+      .min(self.max_delay);
+
+    if self.jitter {
+      rand::thread_rng().gen_range(backoff / 2..=backoff)
+    } else {
+      backoff
+    }
+  }
+}
+
+/// Wrap an [`OsuApiRequester`] to retry the request on transient errors, by the given
+/// [`RetryPolicy`].
+#[derive(Debug, Clone)]
+pub struct Retry<R> {
+  inner: R,
+  policy: RetryPolicy,
+}
+
+impl<R> Retry<R> {
+  pub fn new(inner: R, policy: RetryPolicy) -> Self {
+    Self { inner, policy }
+  }
+
+  pub fn inner(&self) -> &R {
+    &self.inner
+  }
+
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+}
+
+macro_rules! impl_retry {
+  ( $( $name:ident -> $ret:ty ),+ ) => {
+    #[async_trait::async_trait]
+    impl<R> OsuApiRequester for Retry<R>
+    where
+      R: OsuApiRequester + Send + Sync,
+    {
+      $(
+        async fn $name<Q>(&self, query: Q) -> Result<$ret, Error>
+        where Q: TryInto<Query, Error = Error> + Send + Sync
+        {
+          let query: Query = query.try_into()?;
+          let mut attempt = 1;
+          loop {
+            match self.inner.$name(&query).await {
+              Err(err) if err.is_transient() && attempt < self.policy.max_attempts => {
+                tokio::time::sleep(self.policy.delay(attempt, &err)).await;
+                attempt += 1;
+              }
+              ret => return ret,
+            }
+          }
+        }
+      )+
+    }
+  };
+}
+
+with_endpoints!(impl_retry);
+
+#[test]
+fn test_retry_delay() {
+  let policy = RetryPolicy::builder()
+    .base_delay(Duration::from_secs(1))
+    .max_delay(Duration::from_secs(5))
+    .jitter(false)
+    .build();
+  let err = Error::ServerError(reqwest::StatusCode::BAD_GATEWAY);
+
+  assert_eq!(policy.delay(1, &err), Duration::from_secs(1));
+  assert_eq!(policy.delay(2, &err), Duration::from_secs(2));
+  assert_eq!(policy.delay(3, &err), Duration::from_secs(4));
+  assert_eq!(policy.delay(4, &err), Duration::from_secs(5));
+
+  let err = Error::RateLimited {
+    retry_after: Some(Duration::from_secs(42)),
+  };
+  assert_eq!(policy.delay(1, &err), Duration::from_secs(42));
+
+  let policy = RetryPolicy::default();
+  let err = Error::ServerError(reqwest::StatusCode::BAD_GATEWAY);
+  let delay = policy.delay(2, &err);
+  assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_retry() {
+  use crate::{
+    api_v1::GetMatchProp,
+    stub::{stub_v1_client, StubResponse},
+  };
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  let count = AtomicU32::new(0);
+  let (server, client) = stub_v1_client(move |req| {
+    if req.path.starts_with("/api/get_match") {
+      return StubResponse::json(401, r#"{"error": "Please provide a valid API key."}"#);
+    }
+    match count.fetch_add(1, Ordering::SeqCst) {
+      0 => StubResponse::json(502, "<html>Bad Gateway</html>"),
+      1 => StubResponse::json(429, "").header("Retry-After", "0"),
+      _ => StubResponse::json(
+        200,
+        r#"[{"user_id": "1000", "username": "tester", "join_date": "2020-01-01 00:00:00",
+        "country": "JP", "events": []}]"#,
+      ),
+    }
+  })
+  .await;
+
+  let policy = RetryPolicy::builder()
+    .base_delay(Duration::from_millis(1))
+    .build();
+  let client = Retry::new(client, policy);
+
+  // succeed at the third attempt
+  let props = crate::api_v1::GetUserProp::builder()
+    .user_info(1000)
+    .build();
+  let resp = client.get_user(props).await.unwrap();
+  assert_eq!(resp[0].user_id, 1000);
+  assert_eq!(server.requests().len(), 3);
+
+  // invalid key is never retried
+  let props = GetMatchProp::builder().match_id(1).build();
+  let resp = client.get_match(props).await;
+  assert!(matches!(resp, Err(Error::InvalidApiKey)));
+  assert_eq!(server.requests().len(), 4);
+}
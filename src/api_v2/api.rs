@@ -0,0 +1,313 @@
+use std::time::Duration;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+use crate::{
+  api_v2::{
+    models::{
+      AttributesBody, GetBeatmapAttributesResp, GetBeatmapScoresResp, GetSpotlightsResp,
+      GetUsersResp,
+    },
+    Beatmap, BeatmapAttributes, BeatmapLookup, BeatmapUserScore, BeatmapsetSearchResp,
+    CountryRankings, GameMode, GetBeatmapAttributesProp, GetBeatmapScoresProp,
+    GetBeatmapUserScoreProp, GetRankingsProp, GetUserScoresProp, OsuClient, Rankings, Score,
+    SearchBeatmapsetsProp, Spotlight, User, UserExtended, UserId,
+  },
+  common::{error_message, Query},
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+  #[error("the given request param is invalid")]
+  InvalidRequestParams,
+  #[error("fail to build request URL")]
+  InvalidUrl(#[from] url::ParseError),
+  #[error("fail to send request")]
+  NetIO(#[from] reqwest::Error),
+  #[error("fail to get access token: {0}")]
+  Auth(String),
+  #[error("fail to access the token store")]
+  TokenStore(#[source] Box<dyn std::error::Error + Send + Sync>),
+  #[error("the access token is invalid or lacks the required scope")]
+  Unauthorized,
+  #[error("the requested resource is not found")]
+  NotFound,
+  #[error("request too frequently, the API server refuse to respond")]
+  RateLimited {
+    /// Time to wait before next request, from the `Retry-After` response header
+    retry_after: Option<Duration>,
+  },
+  #[error("the API server respond with error status {0}")]
+  ServerError(reqwest::StatusCode),
+  #[error("the API server respond with error: {0}")]
+  Api(String),
+  #[error("fail to deserialize response into expected type")]
+  UnexpectedResponse(#[from] serde_json::Error),
+}
+
+/// Check the response status, and convert API error into [`Error`]
+pub(crate) fn check_response(
+  status: reqwest::StatusCode,
+  retry_after: Option<Duration>,
+  body: &[u8],
+) -> Result<(), Error> {
+  use reqwest::StatusCode;
+
+  match status {
+    status if status.is_success() => Ok(()),
+    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(Error::Unauthorized),
+    StatusCode::NOT_FOUND => Err(Error::NotFound),
+    StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited { retry_after }),
+    status => match error_message(body) {
+      Some(error) => Err(Error::Api(error)),
+      None => Err(Error::ServerError(status)),
+    },
+  }
+}
+
+// Characters that must be encoded in a path segment
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'/')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?')
+  .add(b'`')
+  .add(b'{')
+  .add(b'}');
+
+/// Path segment of the user. Username is prefixed with `@`, so that a username consists of
+/// only numbers won't be recognized as user id.
+pub(crate) fn user_segment(user: &UserId) -> String {
+  match user {
+    UserId::Id(id) => id.to_string(),
+    UserId::Username(name) => format!("@{}", utf8_percent_encode(name, PATH_SEGMENT)),
+  }
+}
+
+impl OsuClient {
+  /// Get the user with the statistics of the given mode, default to the user's favourite mode
+  pub async fn get_user<'u>(
+    &self,
+    user: impl Into<UserId<'u>>,
+    mode: Option<GameMode>,
+  ) -> Result<UserExtended, Error> {
+    let mut path = format!("users/{}", user_segment(&user.into()));
+    if let Some(mode) = mode {
+      path = format!("{path}/{}", mode.ruleset());
+    }
+    self.get(&path, Query::new()).await
+  }
+
+  /// Get the user who granted the access token, requires the `identify` scope
+  pub async fn get_me(&self, mode: Option<GameMode>) -> Result<UserExtended, Error> {
+    let path = match mode {
+      Some(mode) => format!("me/{}", mode.ruleset()),
+      None => "me".to_string(),
+    };
+    self.get(&path, Query::new()).await
+  }
+
+  /// Get up to 50 users by id at once. Users not found are left out. The statistics of every
+  /// ruleset are in [`User::statistics_rulesets`].
+  pub async fn get_users(&self, ids: &[u64]) -> Result<Vec<User>, Error> {
+    if ids.is_empty() || ids.len() > 50 {
+      return Err(Error::InvalidRequestParams);
+    }
+
+    let mut query = Query::new();
+    for id in ids {
+      query.push("ids[]", id);
+    }
+    let resp: GetUsersResp = self.get("users", query).await?;
+
+    Ok(resp.users)
+  }
+
+  /// Get the best, recent, first place or pinned scores of the user
+  pub async fn get_user_scores(&self, prop: GetUserScoresProp) -> Result<Vec<Score>, Error> {
+    let path = format!("users/{}/scores/{}", prop.user_id, prop.score_type.as_str());
+    self.get(&path, prop.try_into()?).await
+  }
+
+  /// Get the beatmap along with its beatmapset
+  pub async fn get_beatmap(&self, beatmap_id: u64) -> Result<Beatmap, Error> {
+    self
+      .get(&format!("beatmaps/{beatmap_id}"), Query::new())
+      .await
+  }
+
+  /// Look up the beatmap by checksum, filename or id
+  pub async fn lookup_beatmap(&self, lookup: BeatmapLookup) -> Result<Beatmap, Error> {
+    self.get("beatmaps/lookup", lookup.into()).await
+  }
+
+  /// Get the difficulty attributes, like star rating, with the mods applied
+  pub async fn get_beatmap_attributes(
+    &self,
+    prop: GetBeatmapAttributesProp,
+  ) -> Result<BeatmapAttributes, Error> {
+    let path = format!("beatmaps/{}/attributes", prop.beatmap_id);
+    let resp: GetBeatmapAttributesResp = self.post(&path, &AttributesBody::from(&prop)).await?;
+
+    Ok(resp.attributes)
+  }
+
+  /// Get the top scores of the beatmap
+  pub async fn get_beatmap_scores(&self, prop: GetBeatmapScoresProp) -> Result<Vec<Score>, Error> {
+    let path = format!("beatmaps/{}/scores", prop.beatmap_id);
+    let resp: GetBeatmapScoresResp = self.get(&path, Query::try_from(&prop)?).await?;
+
+    Ok(resp.scores)
+  }
+
+  /// Search beatmapsets, use the `cursor_string` of the response to get the next page
+  pub async fn search_beatmapsets(
+    &self,
+    prop: &SearchBeatmapsetsProp,
+  ) -> Result<BeatmapsetSearchResp, Error> {
+    self.get("beatmapsets/search", prop.into()).await
+  }
+
+  /// Get the best score of the user on the beatmap
+  pub async fn get_beatmap_user_score(
+    &self,
+    prop: GetBeatmapUserScoreProp,
+  ) -> Result<BeatmapUserScore, Error> {
+    let path = format!("beatmaps/{}/scores/users/{}", prop.beatmap_id, prop.user_id);
+    self.get(&path, Query::from(&prop)).await
+  }
+
+  /// Get the performance, score or charts rankings of the users
+  pub async fn get_rankings(&self, prop: &GetRankingsProp) -> Result<Rankings, Error> {
+    let path = format!(
+      "rankings/{}/{}",
+      prop.mode.ruleset(),
+      prop.ranking_type.as_str()
+    );
+    self.get(&path, Query::try_from(prop)?).await
+  }
+
+  /// Get the rankings of the countries, by the sum of their users' performance. Page number
+  /// starts from 1.
+  pub async fn get_country_rankings(
+    &self,
+    mode: GameMode,
+    page: Option<u32>,
+  ) -> Result<CountryRankings, Error> {
+    let mut query = Query::new();
+    if let Some(page) = page {
+      if page == 0 {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("cursor[page]", page);
+    }
+    let path = format!("rankings/{}/country", mode.ruleset());
+    self.get(&path, query).await
+  }
+
+  /// Get the spotlights, which are the charts and the themed rankings
+  pub async fn get_spotlights(&self) -> Result<Vec<Spotlight>, Error> {
+    let resp: GetSpotlightsResp = self.get("spotlights", Query::new()).await?;
+
+    Ok(resp.spotlights)
+  }
+}
+
+#[test]
+fn test_check_response() {
+  use reqwest::StatusCode;
+
+  assert!(check_response(StatusCode::OK, None, b"{}").is_ok());
+  assert!(matches!(
+    check_response(
+      StatusCode::UNAUTHORIZED,
+      None,
+      br#"{"authentication": "basic"}"#
+    ),
+    Err(Error::Unauthorized)
+  ));
+  assert!(matches!(
+    check_response(StatusCode::NOT_FOUND, None, br#"{"error": null}"#),
+    Err(Error::NotFound)
+  ));
+  assert!(matches!(
+    check_response(StatusCode::UNPROCESSABLE_ENTITY, None, br#"{"error": "invalid cursor"}"#),
+    Err(Error::Api(msg)) if msg == "invalid cursor"
+  ));
+  assert!(matches!(
+    check_response(StatusCode::BAD_GATEWAY, None, b"<html>Bad Gateway</html>"),
+    Err(Error::ServerError(StatusCode::BAD_GATEWAY))
+  ));
+}
+
+#[test]
+fn test_user_segment() {
+  assert_eq!(user_segment(&UserId::Id(2)), "2");
+  assert_eq!(user_segment(&"peppy".into()), "@peppy");
+  assert_eq!(user_segment(&"1234".into()), "@1234");
+  assert_eq!(user_segment(&"Mr Beast/2".into()), "@Mr%20Beast%2F2");
+}
+
+#[tokio::test]
+async fn test_get_users() {
+  use crate::stub::{stub_v2_client, StubResponse};
+
+  let (server, client) = stub_v2_client(|_| {
+    StubResponse::json(
+      200,
+      r#"{"users": [{"avatar_url": "https://a.ppy.sh/2", "country_code": "AU",
+      "default_group": "default", "id": 2, "is_active": true, "is_bot": false,
+      "is_deleted": false, "is_online": false, "is_supporter": true, "last_visit": null,
+      "pm_friends_only": false, "profile_colour": null, "username": "peppy"}]}"#,
+    )
+  })
+  .await;
+
+  let users = client.get_users(&[2, 3]).await.unwrap();
+  assert_eq!(users[0].username, "peppy");
+  assert_eq!(
+    server.requests().last().unwrap().path,
+    "/api/v2/users?ids%5B%5D=2&ids%5B%5D=3"
+  );
+
+  let ids: Vec<u64> = (0..51).collect();
+  assert!(matches!(
+    client.get_users(&ids).await,
+    Err(Error::InvalidRequestParams)
+  ));
+}
+
+#[tokio::test]
+async fn test_get_beatmap_attributes() {
+  use crate::{
+    api_v2::ModsFlag,
+    stub::{stub_v2_client, StubResponse},
+  };
+
+  let (server, client) = stub_v2_client(|_| {
+    StubResponse::json(
+      200,
+      r#"{"attributes": {"star_rating": 7.27318, "max_combo": 791, "aim_difficulty": 3.63211,
+      "speed_difficulty": 3.41107, "speed_note_count": 210.5, "flashlight_difficulty": null,
+      "slider_factor": 0.98, "approach_rate": 8.33, "overall_difficulty": 8.5}}"#,
+    )
+  })
+  .await;
+
+  let prop = GetBeatmapAttributesProp::builder()
+    .beatmap_id(1872396)
+    .mods(vec![ModsFlag::DOUBLETIME, ModsFlag::EASY])
+    .build();
+  let attributes = client.get_beatmap_attributes(prop).await.unwrap();
+  assert!(attributes.stamina_difficulty.is_none());
+
+  let req = server.requests().pop().unwrap();
+  assert_eq!(req.method, "POST");
+  assert_eq!(req.path, "/api/v2/beatmaps/1872396/attributes");
+  assert_eq!(req.header("content-type"), Some("application/json"));
+  assert_eq!(req.body, r#"{"mods":["EZ","DT"]}"#);
+}
@@ -0,0 +1,151 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::api_v2::Error;
+
+/// Tokens are refreshed this long before they expire, so that a token won't expire in flight
+const REFRESH_MARGIN: chrono::Duration = chrono::Duration::seconds(60);
+
+/// Scopes an access token can be granted, read https://osu.ppy.sh/docs/index.html#scopes for
+/// meaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+  Public,
+  Identify,
+  FriendsRead,
+  ChatRead,
+  ChatWrite,
+  ChatWriteManage,
+  Delegate,
+  ForumWrite,
+}
+
+impl Scope {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Public => "public",
+      Self::Identify => "identify",
+      Self::FriendsRead => "friends.read",
+      Self::ChatRead => "chat.read",
+      Self::ChatWrite => "chat.write",
+      Self::ChatWriteManage => "chat.write_manage",
+      Self::Delegate => "delegate",
+      Self::ForumWrite => "forum.write",
+    }
+  }
+}
+
+impl std::fmt::Display for Scope {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_str())
+  }
+}
+
+/// Access token granted by the OAuth token endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessToken {
+  pub access_token: String,
+  /// Only granted by the authorization code grant, for getting a new token when it expires
+  pub refresh_token: Option<String>,
+  pub expires_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+  /// Whether the token is expired or about to expire
+  pub fn is_expired(&self) -> bool {
+    self.expires_at - REFRESH_MARGIN <= Utc::now()
+  }
+}
+
+/// Response of the token endpoint
+#[derive(Deserialize)]
+pub(crate) struct TokenResponse {
+  pub(crate) access_token: String,
+  // seconds until the access token expires
+  pub(crate) expires_in: i64,
+  #[serde(default)]
+  pub(crate) refresh_token: Option<String>,
+}
+
+impl From<TokenResponse> for AccessToken {
+  fn from(resp: TokenResponse) -> Self {
+    Self {
+      access_token: resp.access_token,
+      refresh_token: resp.refresh_token,
+      expires_at: Utc::now() + chrono::Duration::seconds(resp.expires_in),
+    }
+  }
+}
+
+/// Error object the token endpoint respond with, like
+/// `{"error": "invalid_client", "error_description": "Client authentication failed"}`
+#[derive(Deserialize)]
+struct AuthErrorBody {
+  error: String,
+  #[serde(default)]
+  error_description: Option<String>,
+}
+
+/// Post the form to the token endpoint and parse the granted token
+pub(crate) async fn request_token<T>(
+  http: &reqwest::Client,
+  url: reqwest::Url,
+  form: &[(&str, &str)],
+) -> Result<T, Error>
+where
+  T: serde::de::DeserializeOwned,
+{
+  let resp = http.post(url).form(form).send().await?;
+  let status = resp.status();
+  let body = resp.bytes().await?;
+
+  if !status.is_success() {
+    return match serde_json::from_slice(&body) {
+      Ok(AuthErrorBody {
+        error,
+        error_description,
+      }) => Err(Error::Auth(error_description.unwrap_or(error))),
+      Err(_) => Err(Error::ServerError(status)),
+    };
+  }
+
+  Ok(serde_json::from_slice(&body)?)
+}
+
+/// Storage of the tokens granted by users, so that they can be persisted by the application.
+/// The key is decided by the application, like its own user id.
+#[async_trait::async_trait]
+pub trait TokenStore {
+  async fn load(&self, key: &str) -> Result<Option<AccessToken>, Error>;
+  async fn save(&self, key: &str, token: &AccessToken) -> Result<(), Error>;
+}
+
+/// Keep tokens in memory, they are lost when the process exits
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+  tokens: Mutex<HashMap<String, AccessToken>>,
+}
+
+impl MemoryTokenStore {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+#[async_trait::async_trait]
+impl TokenStore for MemoryTokenStore {
+  async fn load(&self, key: &str) -> Result<Option<AccessToken>, Error> {
+    Ok(self.tokens.lock().unwrap().get(key).cloned())
+  }
+
+  async fn save(&self, key: &str, token: &AccessToken) -> Result<(), Error> {
+    self
+      .tokens
+      .lock()
+      .unwrap()
+      .insert(key.to_string(), token.clone());
+    Ok(())
+  }
+}
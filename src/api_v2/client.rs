@@ -0,0 +1,428 @@
+use std::sync::Arc;
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::Mutex;
+use typed_builder::TypedBuilder;
+
+use crate::{
+  api_v2::{
+    api::check_response,
+    auth::{request_token, AccessToken, Scope, TokenResponse, TokenStore},
+    Error,
+  },
+  common::{retry_after, Query},
+  ratelimit::RateLimiter,
+};
+
+/// A client of the v2 API, authenticated by the client credentials grant. It gets an access
+/// token with the `public` scope on the first request, and gets a new one before it expires.
+/// Use [`OsuClient::as_user`] to send requests as a user who granted access through the
+/// authorization code grant.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), osu_api::api_v2::Error> {
+/// use osu_api::api_v2::OsuClient;
+///
+/// let client = OsuClient::new(1234, "YOUR_CLIENT_SECRET");
+/// let token = client.access_token().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(TypedBuilder, Debug, Clone)]
+pub struct OsuClient {
+  client_id: u64,
+  #[builder(setter(into))]
+  client_secret: String,
+  #[builder(default)]
+  http: reqwest::Client,
+  /// Base URL of the server, the API is at `api/v2/` and the token endpoint is at
+  /// `oauth/token` under it. Default to the official server.
+  #[builder(default = reqwest::Url::parse(OsuClient::DEFAULT_BASE_URL).unwrap())]
+  base_url: reqwest::Url,
+  /// Wait for the limiter before every request, including the token requests and the pages of
+  /// the paginated requests
+  #[builder(default, setter(strip_option))]
+  limiter: Option<Arc<RateLimiter>>,
+  /// Callback URL registered with the OAuth application, required by the authorization code
+  /// grant
+  #[builder(default, setter(into, strip_option))]
+  redirect_uri: Option<String>,
+  // Shared by the clones, so that they don't request token separately
+  #[builder(default, setter(skip))]
+  token: Arc<Mutex<Option<AccessToken>>>,
+  // Send requests as this user instead of the client itself
+  #[builder(default, setter(skip))]
+  user: Option<UserAuth>,
+}
+
+/// User the client acts as, and where the user's token is kept
+#[derive(Clone)]
+struct UserAuth {
+  key: String,
+  store: Arc<dyn TokenStore + Send + Sync>,
+}
+
+impl std::fmt::Debug for UserAuth {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("UserAuth").field("key", &self.key).finish()
+  }
+}
+
+impl OsuClient {
+  /// Base URL of the official osu! server
+  pub const DEFAULT_BASE_URL: &'static str = "https://osu.ppy.sh/";
+
+  /// Create a client with default HTTP client and base URL
+  pub fn new(client_id: u64, client_secret: impl Into<String>) -> Self {
+    Self::builder()
+      .client_id(client_id)
+      .client_secret(client_secret)
+      .build()
+  }
+
+  /// Join the path to the base URL. Base URL without trailing slash is treated as a directory.
+  fn url(&self, path: &str) -> Result<reqwest::Url, Error> {
+    let mut base = self.base_url.clone();
+    if !base.path().ends_with('/') {
+      base.set_path(&format!("{}/", base.path()));
+    }
+
+    Ok(base.join(path)?)
+  }
+
+  /// Wait for the limiter if there is one. Token requests count as well.
+  async fn throttle(&self) {
+    if let Some(limiter) = &self.limiter {
+      limiter.acquire().await;
+    }
+  }
+
+  async fn token_request(&self, form: &[(&str, &str)]) -> Result<AccessToken, Error> {
+    self.throttle().await;
+    let resp: TokenResponse = request_token(&self.http, self.url("oauth/token")?, form).await?;
+
+    Ok(resp.into())
+  }
+
+  /// Get the cached access token, or request a new one if there is no token or it is about to
+  /// expire
+  pub async fn access_token(&self) -> Result<String, Error> {
+    // Hold the lock while requesting, so that concurrent requests share the same new token
+    let mut token = self.token.lock().await;
+    if let Some(token) = token.as_ref().filter(|token| !token.is_expired()) {
+      return Ok(token.access_token.clone());
+    }
+
+    let new_token = match &self.user {
+      Some(user) => self.user_token(user).await?,
+      None => self.client_credentials().await?,
+    };
+    let access_token = new_token.access_token.clone();
+    *token = Some(new_token);
+
+    Ok(access_token)
+  }
+
+  async fn client_credentials(&self) -> Result<AccessToken, Error> {
+    let client_id = self.client_id.to_string();
+    self
+      .token_request(&[
+        ("client_id", &client_id),
+        ("client_secret", &self.client_secret),
+        ("grant_type", "client_credentials"),
+        ("scope", "public"),
+      ])
+      .await
+  }
+
+  /// Load the user's token from the store, and refresh it if it is expired
+  async fn user_token(&self, user: &UserAuth) -> Result<AccessToken, Error> {
+    let token = user
+      .store
+      .load(&user.key)
+      .await?
+      .ok_or_else(|| Error::Auth(format!("no token is stored for user {}", user.key)))?;
+    if !token.is_expired() {
+      return Ok(token);
+    }
+
+    let Some(refresh_token) = token.refresh_token else {
+      return Err(Error::Auth(format!(
+        "token of user {} is expired",
+        user.key
+      )));
+    };
+    let mut new_token = self.refresh_token(&refresh_token).await?;
+    // Keep using the old refresh token if the server doesn't rotate it
+    new_token.refresh_token.get_or_insert(refresh_token);
+    user.store.save(&user.key, &new_token).await?;
+
+    Ok(new_token)
+  }
+
+  fn redirect_uri(&self) -> Result<&str, Error> {
+    self
+      .redirect_uri
+      .as_deref()
+      .ok_or_else(|| Error::Auth("redirect_uri is required by the authorization code grant".into()))
+  }
+
+  /// URL to send the user to for granting access with the given scopes. The `state` is sent
+  /// back to the redirect URI along with the code, for preventing CSRF.
+  pub fn authorize_url(&self, scopes: &[Scope], state: &str) -> Result<reqwest::Url, Error> {
+    let scope: Vec<_> = scopes.iter().map(Scope::as_str).collect();
+
+    let mut query = Query::new();
+    query.push("client_id", self.client_id);
+    query.push("redirect_uri", self.redirect_uri()?);
+    query.push("response_type", "code");
+    query.push("scope", scope.join(" "));
+    query.push("state", state);
+
+    let mut url = self.url("oauth/authorize")?;
+    url.set_query(Some(&query.into_query_str()));
+
+    Ok(url)
+  }
+
+  /// Exchange the code sent to the redirect URI for the user's token
+  pub async fn exchange_code(&self, code: &str) -> Result<AccessToken, Error> {
+    let client_id = self.client_id.to_string();
+    self
+      .token_request(&[
+        ("client_id", &client_id),
+        ("client_secret", &self.client_secret),
+        ("code", code),
+        ("grant_type", "authorization_code"),
+        ("redirect_uri", self.redirect_uri()?),
+      ])
+      .await
+  }
+
+  /// Get a new token of the user with the refresh token
+  pub async fn refresh_token(&self, refresh_token: &str) -> Result<AccessToken, Error> {
+    let client_id = self.client_id.to_string();
+    self
+      .token_request(&[
+        ("client_id", &client_id),
+        ("client_secret", &self.client_secret),
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+      ])
+      .await
+  }
+
+  /// Create a client that sends requests as the user, with the token kept in the store under
+  /// the key. The token is refreshed and saved back to the store when it expires.
+  pub fn as_user(&self, key: impl Into<String>, store: Arc<dyn TokenStore + Send + Sync>) -> Self {
+    Self {
+      token: Arc::default(),
+      user: Some(UserAuth {
+        key: key.into(),
+        store,
+      }),
+      ..self.clone()
+    }
+  }
+
+  /// Send GET request to the path under `api/v2/`, like `users/2/osu`, and deserialize the
+  /// response body
+  pub async fn get<T: DeserializeOwned>(&self, path: &str, query: Query) -> Result<T, Error> {
+    let mut url = self.url(&format!("api/v2/{path}"))?;
+    if !query.pairs().is_empty() {
+      url.set_query(Some(&query.into_query_str()));
+    }
+
+    self.send(self.http.get(url)).await
+  }
+
+  /// Send POST request with the JSON body to the path under `api/v2/`, and deserialize the
+  /// response body
+  pub async fn post<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+  where
+    T: DeserializeOwned,
+    B: Serialize + ?Sized,
+  {
+    let url = self.url(&format!("api/v2/{path}"))?;
+    let req = self
+      .http
+      .post(url)
+      .header(reqwest::header::CONTENT_TYPE, "application/json")
+      .body(serde_json::to_vec(body)?);
+
+    self.send(req).await
+  }
+
+  async fn send<T: DeserializeOwned>(&self, req: reqwest::RequestBuilder) -> Result<T, Error> {
+    // After getting the token, which waits for the limiter itself when requesting a new one
+    let token = self.access_token().await?;
+    self.throttle().await;
+    let resp = req.bearer_auth(token).send().await?;
+    let status = resp.status();
+    let retry_after = retry_after(resp.headers());
+    let body = resp.bytes().await?;
+    check_response(status, retry_after, &body)?;
+
+    Ok(serde_json::from_slice(&body)?)
+  }
+}
+
+#[tokio::test]
+async fn test_client_credentials() {
+  use crate::stub::{StubResponse, StubServer};
+  use std::sync::atomic::{AtomicU32, Ordering};
+
+  // The first token expires immediately, the second one lasts a day
+  let issued = AtomicU32::new(0);
+  let server = StubServer::start(move |req| {
+    if req.path == "/oauth/token" {
+      let n = issued.fetch_add(1, Ordering::SeqCst);
+      let expires_in = if n == 0 { 0 } else { 86400 };
+      return StubResponse::json(
+        200,
+        format!(
+          r#"{{"token_type": "Bearer", "expires_in": {expires_in}, "access_token": "token-{n}"}}"#
+        ),
+      );
+    }
+    match req.header("authorization") {
+      Some("Bearer token-1") => StubResponse::json(200, r#"{"id": 2}"#),
+      _ => StubResponse::json(401, r#"{"authentication": "basic"}"#),
+    }
+  })
+  .await;
+
+  let client = OsuClient::builder()
+    .client_id(1234)
+    .client_secret("secret")
+    .base_url(server.url("/"))
+    .build();
+
+  assert_eq!(client.access_token().await.unwrap(), "token-0");
+  for _ in 0..2 {
+    let user: serde_json::Value = client.get("users/2/osu", Query::new()).await.unwrap();
+    assert_eq!(user["id"], 2);
+  }
+
+  let requests = server.requests();
+  let tokens: Vec<_> = requests
+    .iter()
+    .filter(|req| req.path == "/oauth/token")
+    .collect();
+  // token-0 is expired and refreshed, and token-1 is reused
+  assert_eq!(tokens.len(), 2);
+  assert_eq!(tokens[0].method, "POST");
+  assert!(tokens[0].body.contains("grant_type=client_credentials"));
+  assert!(tokens[0].body.contains("scope=public"));
+  assert!(tokens[0].body.contains("client_id=1234"));
+  assert_eq!(requests.last().unwrap().path, "/api/v2/users/2/osu");
+}
+
+#[tokio::test]
+async fn test_invalid_client() {
+  use crate::stub::{StubResponse, StubServer};
+
+  let server = StubServer::start(|_| {
+    StubResponse::json(
+      401,
+      r#"{"error": "invalid_client", "error_description": "Client authentication failed"}"#,
+    )
+  })
+  .await;
+  let client = OsuClient::builder()
+    .client_id(1234)
+    .client_secret("wrong")
+    .base_url(server.url("/"))
+    .build();
+
+  let resp = client.access_token().await;
+  assert!(matches!(resp, Err(Error::Auth(msg)) if msg == "Client authentication failed"));
+}
+
+#[test]
+fn test_authorize_url() {
+  let client = OsuClient::builder()
+    .client_id(1234)
+    .client_secret("secret")
+    .redirect_uri("https://example.com/callback")
+    .build();
+
+  let url = client
+    .authorize_url(&[Scope::Public, Scope::ChatWrite], "random-state")
+    .unwrap();
+  assert_eq!(
+    url.as_str(),
+    "https://osu.ppy.sh/oauth/authorize?client_id=1234\
+    &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&response_type=code\
+    &scope=public+chat.write&state=random-state"
+  );
+
+  let client = OsuClient::new(1234, "secret");
+  assert!(matches!(
+    client.authorize_url(&[Scope::Public], ""),
+    Err(Error::Auth(_))
+  ));
+}
+
+#[tokio::test]
+async fn test_authorization_code() {
+  use crate::{
+    api_v2::MemoryTokenStore,
+    stub::{StubResponse, StubServer},
+  };
+
+  let server = StubServer::start(|req| {
+    if req.path != "/oauth/token" {
+      return match req.header("authorization") {
+        Some("Bearer refreshed") => StubResponse::json(200, r#"{"id": 2}"#),
+        _ => StubResponse::json(401, r#"{"authentication": "basic"}"#),
+      };
+    }
+    if req.body.contains("grant_type=authorization_code") && req.body.contains("code=the-code") {
+      // expires immediately
+      StubResponse::json(
+        200,
+        r#"{"token_type": "Bearer", "expires_in": 0, "access_token": "granted",
+        "refresh_token": "refresh-1"}"#,
+      )
+    } else if req
+      .body
+      .contains("grant_type=refresh_token&refresh_token=refresh-1")
+    {
+      StubResponse::json(
+        200,
+        r#"{"token_type": "Bearer", "expires_in": 86400, "access_token": "refreshed",
+        "refresh_token": "refresh-2"}"#,
+      )
+    } else {
+      StubResponse::json(400, r#"{"error": "invalid_grant"}"#)
+    }
+  })
+  .await;
+
+  let client = OsuClient::builder()
+    .client_id(1234)
+    .client_secret("secret")
+    .base_url(server.url("/"))
+    .redirect_uri("http://localhost/callback")
+    .build();
+  let token = client.exchange_code("the-code").await.unwrap();
+  assert_eq!(token.refresh_token.as_deref(), Some("refresh-1"));
+
+  let store = Arc::new(MemoryTokenStore::new());
+  store.save("alice", &token).await.unwrap();
+
+  // the expired token is refreshed and saved back
+  let user = client.as_user("alice", store.clone());
+  let me: serde_json::Value = user.get("me", Query::new()).await.unwrap();
+  assert_eq!(me["id"], 2);
+  let saved = store.load("alice").await.unwrap().unwrap();
+  assert_eq!(saved.access_token, "refreshed");
+  assert_eq!(saved.refresh_token.as_deref(), Some("refresh-2"));
+
+  let unknown = client.as_user("bob", store);
+  assert!(matches!(unknown.access_token().await, Err(Error::Auth(_))));
+  assert!(
+    matches!(client.exchange_code("bad-code").await, Err(Error::Auth(msg)) if msg == "invalid_grant")
+  );
+}
@@ -0,0 +1,19 @@
+mod api;
+mod auth;
+mod client;
+mod models;
+mod paginate;
+
+pub use api::Error;
+pub use auth::{AccessToken, MemoryTokenStore, Scope, TokenStore};
+pub use client::OsuClient;
+pub use models::{
+  Beatmap, BeatmapAttributes, BeatmapLookup, BeatmapUserScore, Beatmapset, BeatmapsetCovers,
+  BeatmapsetSearchResp, Country, CountryRanking, CountryRankings, GameMode, Genre,
+  GetBeatmapAttributesProp, GetBeatmapScoresProp, GetBeatmapUserScoreProp, GetRankingsProp,
+  GetUserScoresProp, Grade, GradeCounts, Language, ManiaVariant, ModsFlag, RankHistory, RankStatus,
+  RankingCursor, RankingType, Rankings, Score, ScoreStatistics, ScoreType, ScoreWeight,
+  SearchBeatmapsetsProp, SearchSort, SearchStatus, SortDirection, Spotlight, SpotlightType, User,
+  UserBadge, UserCover, UserExtended, UserId, UserLevel, UserRanking, UserStatistics,
+};
+pub use paginate::{Page, Paginator};
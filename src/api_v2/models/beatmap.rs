@@ -0,0 +1,343 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use super::{de::ruleset_to_game_mode, GameMode, ModsFlag, Score};
+use crate::{api_v2::Error, common::Query};
+
+/// Combine a list of mods into their acronyms
+fn mods_acronyms(mods: &[ModsFlag]) -> Vec<&'static str> {
+  mods.iter().copied().collect::<ModsFlag>().acronyms()
+}
+
+/// Ranked status of a beatmap or beatmapset
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RankStatus {
+  Graveyard,
+  Wip,
+  Pending,
+  Ranked,
+  Approved,
+  Qualified,
+  Loved,
+}
+
+/// A difficulty of a beatmapset. Attributes other than the compact form are only returned by
+/// some of the endpoints.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Beatmap {
+  pub id: u64,
+  pub beatmapset_id: u64,
+  // star rating without mods
+  pub difficulty_rating: f64,
+  #[serde(deserialize_with = "ruleset_to_game_mode")]
+  pub mode: GameMode,
+  pub status: RankStatus,
+  // seconds from the first note to the last note, including breaks
+  pub total_length: u32,
+  // mapper of this difficulty
+  pub user_id: u64,
+  // difficulty name
+  pub version: String,
+  // md5 hash of the beatmap file
+  #[serde(default)]
+  pub checksum: Option<String>,
+  #[serde(default)]
+  pub max_combo: Option<u32>,
+  // overall difficulty
+  #[serde(default)]
+  pub accuracy: Option<f32>,
+  // approach rate
+  #[serde(default)]
+  pub ar: Option<f32>,
+  // circle size
+  #[serde(default)]
+  pub cs: Option<f32>,
+  // HP drain rate
+  #[serde(default)]
+  pub drain: Option<f32>,
+  #[serde(default)]
+  pub bpm: Option<f64>,
+  // seconds from the first note to the last note, excluding breaks
+  #[serde(default)]
+  pub hit_length: Option<u32>,
+  #[serde(default)]
+  pub count_circles: Option<u32>,
+  #[serde(default)]
+  pub count_sliders: Option<u32>,
+  #[serde(default)]
+  pub count_spinners: Option<u32>,
+  // whether the beatmap is converted from standard mode
+  #[serde(default)]
+  pub convert: Option<bool>,
+  #[serde(default)]
+  pub last_updated: Option<DateTime<Utc>>,
+  #[serde(default)]
+  pub playcount: Option<u64>,
+  #[serde(default)]
+  pub passcount: Option<u64>,
+  #[serde(default)]
+  pub url: Option<String>,
+  #[serde(default)]
+  pub beatmapset: Option<Beatmapset>,
+}
+
+/// A beatmapset, which contains one or more difficulties
+#[derive(Deserialize, Debug, Clone)]
+pub struct Beatmapset {
+  pub id: u64,
+  pub artist: String,
+  pub artist_unicode: String,
+  pub title: String,
+  pub title_unicode: String,
+  // username of the mapper
+  pub creator: String,
+  pub user_id: u64,
+  pub source: String,
+  pub covers: BeatmapsetCovers,
+  pub favourite_count: u32,
+  pub play_count: u64,
+  pub nsfw: bool,
+  pub video: bool,
+  pub preview_url: String,
+  pub status: RankStatus,
+  // attributes below are not returned by the compact form
+  #[serde(default)]
+  pub bpm: Option<f64>,
+  // space separated
+  #[serde(default)]
+  pub tags: Option<String>,
+  #[serde(default)]
+  pub ranked_date: Option<DateTime<Utc>>,
+  #[serde(default)]
+  pub submitted_date: Option<DateTime<Utc>>,
+  #[serde(default)]
+  pub last_updated: Option<DateTime<Utc>>,
+  #[serde(default)]
+  pub storyboard: Option<bool>,
+  #[serde(default)]
+  pub beatmaps: Vec<Beatmap>,
+}
+
+/// URLs of the cover images in different sizes
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeatmapsetCovers {
+  pub cover: String,
+  pub card: String,
+  pub list: String,
+  pub slimcover: String,
+}
+
+/// Key to look up a beatmap by
+#[derive(Debug, Clone)]
+pub enum BeatmapLookup {
+  /// md5 hash of the beatmap file
+  Checksum(String),
+  /// Name of the beatmap file, like `Artist - Title (Creator) [Version].osu`
+  Filename(String),
+  Id(u64),
+}
+
+impl From<BeatmapLookup> for Query {
+  fn from(lookup: BeatmapLookup) -> Self {
+    let mut query = Query::new();
+    match lookup {
+      BeatmapLookup::Checksum(checksum) => query.push("checksum", checksum),
+      BeatmapLookup::Filename(filename) => query.push("filename", filename),
+      BeatmapLookup::Id(id) => query.push("id", id),
+    }
+    query
+  }
+}
+
+#[derive(TypedBuilder, Debug)]
+#[builder(
+  builder_type_doc = "Builder for creating request to `/beatmaps/{beatmap}/attributes`,
+read https://osu.ppy.sh/docs/index.html#get-beatmap-attributes for meaning"
+)]
+pub struct GetBeatmapAttributesProp {
+  pub(crate) beatmap_id: u64,
+  /// Get the attributes with the given mods applied
+  #[builder(default = Vec::new())]
+  pub(crate) mods: Vec<ModsFlag>,
+  /// Get the attributes of the beatmap converted to the mode, default to the beatmap's mode
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+}
+
+/// Request body of the attributes endpoint
+#[derive(Serialize)]
+pub(crate) struct AttributesBody {
+  mods: Vec<&'static str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  ruleset: Option<&'static str>,
+}
+
+impl From<&GetBeatmapAttributesProp> for AttributesBody {
+  fn from(prop: &GetBeatmapAttributesProp) -> Self {
+    Self {
+      mods: mods_acronyms(&prop.mods),
+      ruleset: prop.mode.map(|mode| mode.ruleset()),
+    }
+  }
+}
+
+/// Difficulty attributes of a beatmap. Attributes other than star rating and max combo are
+/// only returned for the mode noted.
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeatmapAttributes {
+  pub star_rating: f64,
+  pub max_combo: u32,
+  // standard
+  #[serde(default)]
+  pub aim_difficulty: Option<f64>,
+  // standard
+  #[serde(default)]
+  pub speed_difficulty: Option<f64>,
+  // standard
+  #[serde(default)]
+  pub speed_note_count: Option<f64>,
+  // standard
+  #[serde(default)]
+  pub flashlight_difficulty: Option<f64>,
+  // standard
+  #[serde(default)]
+  pub slider_factor: Option<f64>,
+  // standard and catch
+  #[serde(default)]
+  pub approach_rate: Option<f64>,
+  // standard
+  #[serde(default)]
+  pub overall_difficulty: Option<f64>,
+  // taiko
+  #[serde(default)]
+  pub stamina_difficulty: Option<f64>,
+  // taiko
+  #[serde(default)]
+  pub rhythm_difficulty: Option<f64>,
+  // taiko
+  #[serde(default)]
+  pub colour_difficulty: Option<f64>,
+  // taiko and mania, in milliseconds
+  #[serde(default)]
+  pub great_hit_window: Option<f64>,
+  // mania
+  #[serde(default)]
+  pub score_multiplier: Option<f64>,
+}
+
+/// Response of the attributes endpoint
+#[derive(Deserialize)]
+pub(crate) struct GetBeatmapAttributesResp {
+  pub(crate) attributes: BeatmapAttributes,
+}
+
+#[derive(TypedBuilder, Debug)]
+#[builder(
+  builder_type_doc = "Builder for creating request to `/beatmaps/{beatmap}/scores`,
+read https://osu.ppy.sh/docs/index.html#get-beatmap-scores for meaning"
+)]
+pub struct GetBeatmapScoresProp {
+  pub(crate) beatmap_id: u64,
+  /// Get scores of the beatmap converted to the mode, default to the beatmap's mode
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Only return scores played with exactly the given mods combination
+  #[builder(default = Vec::new())]
+  pub(crate) mods: Vec<ModsFlag>,
+  /// Amount of results. Range between 1 and 100.
+  #[builder(default, setter(strip_option))]
+  pub(crate) limit: Option<u8>,
+}
+
+/// Query of the mode and mods filters of the beatmap scores
+fn filter_query(mode: Option<GameMode>, mods: &[ModsFlag]) -> Query {
+  let mut query = Query::new();
+
+  if let Some(mode) = mode {
+    query.push("mode", mode.ruleset());
+  }
+
+  for acronym in mods_acronyms(mods) {
+    query.push("mods[]", acronym);
+  }
+
+  query
+}
+
+impl TryFrom<&GetBeatmapScoresProp> for Query {
+  type Error = Error;
+
+  fn try_from(prop: &GetBeatmapScoresProp) -> Result<Self, Self::Error> {
+    let mut query = filter_query(prop.mode, &prop.mods);
+
+    if let Some(limit) = prop.limit {
+      if !(1..=100).contains(&limit) {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("limit", limit);
+    }
+
+    Ok(query)
+  }
+}
+
+#[derive(TypedBuilder, Debug)]
+#[builder(builder_type_doc = "Builder for creating request to
+`/beatmaps/{beatmap}/scores/users/{user}`, read
+https://osu.ppy.sh/docs/index.html#get-a-user-beatmap-score for meaning")]
+pub struct GetBeatmapUserScoreProp {
+  pub(crate) beatmap_id: u64,
+  pub(crate) user_id: u64,
+  /// Get score of the beatmap converted to the mode, default to the beatmap's mode
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Only return score played with exactly the given mods combination
+  #[builder(default = Vec::new())]
+  pub(crate) mods: Vec<ModsFlag>,
+}
+
+impl From<&GetBeatmapUserScoreProp> for Query {
+  fn from(prop: &GetBeatmapUserScoreProp) -> Self {
+    filter_query(prop.mode, &prop.mods)
+  }
+}
+
+/// Response of the beatmap scores endpoint
+#[derive(Deserialize)]
+pub(crate) struct GetBeatmapScoresResp {
+  pub(crate) scores: Vec<Score>,
+}
+
+/// Best score of a user on a beatmap
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeatmapUserScore {
+  // rank on the leaderboard of the beatmap
+  pub position: u32,
+  pub score: Score,
+}
+
+#[test]
+fn test_beatmap_queries() {
+  let query = Query::from(BeatmapLookup::Checksum("1c5d2ff3".to_string()));
+  assert_eq!(query.into_query_str(), "checksum=1c5d2ff3");
+
+  let prop = GetBeatmapScoresProp::builder()
+    .beatmap_id(1872396)
+    .mode(GameMode::Standard)
+    .mods(vec![ModsFlag::DOUBLETIME, ModsFlag::HIDDEN])
+    .build();
+  let query = Query::try_from(&prop).unwrap();
+  assert_eq!(
+    query.into_query_str(),
+    "mode=osu&mods%5B%5D=HD&mods%5B%5D=DT"
+  );
+
+  let prop = GetBeatmapAttributesProp::builder()
+    .beatmap_id(1872396)
+    .mods(vec![ModsFlag::DOUBLETIME, ModsFlag::EASY])
+    .build();
+  let body = serde_json::to_string(&AttributesBody::from(&prop)).unwrap();
+  assert_eq!(body, r#"{"mods":["EZ","DT"]}"#);
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+
+use super::{GameMode, UserStatistics};
+
+pub(crate) fn ruleset_to_game_mode<'de, D>(d: D) -> Result<GameMode, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let name: String = Deserialize::deserialize(d)?;
+  GameMode::from_ruleset(&name)
+    .ok_or_else(|| serde::de::Error::custom(format!("Unknown ruleset {name}")))
+}
+
+/// Deserialize the object keyed by ruleset names, like `statistics_rulesets`. Rulesets
+/// without statistics and unknown rulesets are left out.
+pub(crate) fn ruleset_to_statistics<'de, D>(
+  d: D,
+) -> Result<HashMap<GameMode, UserStatistics>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  let map: HashMap<String, Option<UserStatistics>> = Deserialize::deserialize(d)?;
+  Ok(
+    map
+      .into_iter()
+      .filter_map(|(name, stats)| Some((GameMode::from_ruleset(&name)?, stats?)))
+      .collect(),
+  )
+}
@@ -0,0 +1,29 @@
+mod beatmap;
+mod de;
+mod ranking;
+mod score;
+mod search;
+mod user;
+
+pub(crate) use beatmap::{AttributesBody, GetBeatmapAttributesResp, GetBeatmapScoresResp};
+pub use beatmap::{
+  Beatmap, BeatmapAttributes, BeatmapLookup, BeatmapUserScore, Beatmapset, BeatmapsetCovers,
+  GetBeatmapAttributesProp, GetBeatmapScoresProp, GetBeatmapUserScoreProp, RankStatus,
+};
+pub(crate) use ranking::GetSpotlightsResp;
+pub use ranking::{
+  CountryRanking, CountryRankings, GetRankingsProp, ManiaVariant, RankingCursor, RankingType,
+  Rankings, Spotlight, SpotlightType, UserRanking,
+};
+pub use score::{GetUserScoresProp, Grade, Score, ScoreStatistics, ScoreType, ScoreWeight};
+pub use search::{
+  BeatmapsetSearchResp, SearchBeatmapsetsProp, SearchSort, SearchStatus, SortDirection,
+};
+
+pub(crate) use user::GetUsersResp;
+pub use user::{
+  Country, GradeCounts, RankHistory, User, UserBadge, UserCover, UserExtended, UserLevel,
+  UserStatistics,
+};
+
+pub use crate::common::{GameMode, Genre, Language, ModsFlag, UserId};
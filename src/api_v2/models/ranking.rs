@@ -0,0 +1,272 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+use super::{Beatmapset, Country, GameMode, User, UserStatistics};
+use crate::{api_v2::Error, common::Query};
+
+/// Type of the user rankings. Rankings of countries are requested by
+/// [`OsuClient::get_country_rankings`](crate::api_v2::OsuClient::get_country_rankings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingType {
+  Performance,
+  Score,
+  /// Rankings of the spotlight, like the monthly charts
+  Charts,
+}
+
+impl RankingType {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Performance => "performance",
+      Self::Score => "score",
+      Self::Charts => "charts",
+    }
+  }
+}
+
+/// Key count variant of the osu!mania performance rankings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManiaVariant {
+  Key4,
+  Key7,
+}
+
+impl ManiaVariant {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Key4 => "4k",
+      Self::Key7 => "7k",
+    }
+  }
+}
+
+#[derive(TypedBuilder, Debug, Clone)]
+#[builder(
+  builder_type_doc = "Builder for creating request to `/rankings/{mode}/{type}`,
+read https://osu.ppy.sh/docs/index.html#get-ranking for meaning"
+)]
+pub struct GetRankingsProp {
+  pub(crate) mode: GameMode,
+  pub(crate) ranking_type: RankingType,
+  /// Two letters country code, only for the performance rankings
+  #[builder(default, setter(into, strip_option))]
+  pub(crate) country: Option<String>,
+  /// Only for the osu!mania performance rankings
+  #[builder(default, setter(strip_option))]
+  pub(crate) variant: Option<ManiaVariant>,
+  /// Spotlight id of the charts rankings, default to the latest spotlight
+  #[builder(default, setter(strip_option))]
+  pub(crate) spotlight: Option<u64>,
+  /// Only rank the friends of the user, requires acting as user
+  #[builder(setter(strip_bool))]
+  pub(crate) friends_only: bool,
+  /// Page number starting from 1, 50 entries per page
+  #[builder(default, setter(strip_option))]
+  pub(crate) page: Option<u32>,
+}
+
+impl TryFrom<&GetRankingsProp> for Query {
+  type Error = Error;
+
+  fn try_from(prop: &GetRankingsProp) -> Result<Self, Self::Error> {
+    let mut query = Query::new();
+
+    if let Some(country) = &prop.country {
+      if prop.ranking_type != RankingType::Performance {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("country", country);
+    }
+
+    if let Some(variant) = prop.variant {
+      if prop.ranking_type != RankingType::Performance || prop.mode != GameMode::Mania {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("variant", variant.as_str());
+    }
+
+    if let Some(spotlight) = prop.spotlight {
+      if prop.ranking_type != RankingType::Charts {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("spotlight", spotlight);
+    }
+
+    if prop.friends_only {
+      query.push("filter", "friends");
+    }
+
+    if let Some(page) = prop.page {
+      if page == 0 {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("cursor[page]", page);
+    }
+
+    Ok(query)
+  }
+}
+
+/// Position of the next page of the rankings
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct RankingCursor {
+  pub page: u32,
+}
+
+/// Statistics of the user in the rankings
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserRanking {
+  #[serde(flatten)]
+  pub statistics: UserStatistics,
+  pub user: User,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Rankings {
+  pub ranking: Vec<UserRanking>,
+  // Null if this is the last page
+  #[serde(default)]
+  pub cursor: Option<RankingCursor>,
+  // Count of all the ranked users, up to 10000
+  pub total: u32,
+  // Only for the charts rankings
+  #[serde(default)]
+  pub spotlight: Option<Spotlight>,
+  // Beatmapsets of the spotlight, only for the charts rankings
+  #[serde(default)]
+  pub beatmapsets: Vec<Beatmapset>,
+}
+
+/// Statistics of the country in the rankings
+#[derive(Deserialize, Debug, Clone)]
+pub struct CountryRanking {
+  // two letters ISO 3166-1 alpha-2 code
+  pub code: String,
+  pub active_users: u32,
+  pub play_count: u64,
+  pub ranked_score: u64,
+  pub performance: f64,
+  pub country: Country,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CountryRankings {
+  pub ranking: Vec<CountryRanking>,
+  // Null if this is the last page
+  #[serde(default)]
+  pub cursor: Option<RankingCursor>,
+  pub total: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SpotlightType {
+  Monthly,
+  BestOf,
+  Theme,
+  Special,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Spotlight {
+  pub id: u64,
+  pub name: String,
+  #[serde(rename = "type")]
+  pub spotlight_type: SpotlightType,
+  pub start_date: DateTime<Utc>,
+  pub end_date: DateTime<Utc>,
+  // Whether the spotlight has different beatmaps for each mode
+  pub mode_specific: bool,
+  // Only returned along with the rankings
+  #[serde(default)]
+  pub participant_count: Option<u32>,
+}
+
+/// Response of the `/spotlights` endpoint
+#[derive(Deserialize)]
+pub(crate) struct GetSpotlightsResp {
+  pub(crate) spotlights: Vec<Spotlight>,
+}
+
+#[test]
+fn test_rankings_query() {
+  let prop = GetRankingsProp::builder()
+    .mode(GameMode::Mania)
+    .ranking_type(RankingType::Performance)
+    .country("JP")
+    .variant(ManiaVariant::Key4)
+    .friends_only()
+    .page(3)
+    .build();
+  assert_eq!(
+    Query::try_from(&prop).unwrap().into_query_str(),
+    "country=JP&variant=4k&filter=friends&cursor%5Bpage%5D=3"
+  );
+
+  let prop = GetRankingsProp::builder()
+    .mode(GameMode::Standard)
+    .ranking_type(RankingType::Charts)
+    .spotlight(270)
+    .build();
+  assert_eq!(
+    Query::try_from(&prop).unwrap().into_query_str(),
+    "spotlight=270"
+  );
+
+  // variant is only for mania
+  let prop = GetRankingsProp::builder()
+    .mode(GameMode::Taiko)
+    .ranking_type(RankingType::Performance)
+    .variant(ManiaVariant::Key7)
+    .build();
+  assert!(Query::try_from(&prop).is_err());
+  // country is only for performance
+  let prop = GetRankingsProp::builder()
+    .mode(GameMode::Standard)
+    .ranking_type(RankingType::Score)
+    .country("JP")
+    .build();
+  assert!(Query::try_from(&prop).is_err());
+}
+
+#[test]
+fn test_deserialize_rankings() {
+  let resp = r#"{
+    "cursor": {"page": 2},
+    "ranking": [{"count_300": 10, "count_100": 2, "count_50": 0, "count_miss": 1,
+      "level": {"current": 101, "progress": 37}, "global_rank": 1, "pp": 25000.5,
+      "ranked_score": 100000, "hit_accuracy": 98.9, "play_count": 100, "play_time": 3600,
+      "total_score": 200000, "total_hits": 12, "maximum_combo": 3000,
+      "replays_watched_by_others": 5, "is_ranked": true,
+      "grade_counts": {"ss": 1, "ssh": 2, "s": 3, "sh": 4, "a": 5},
+      "user": {"avatar_url": "https://a.ppy.sh/7562902", "country_code": "AU",
+        "default_group": "default", "id": 7562902, "is_active": true, "is_bot": false,
+        "is_deleted": false, "is_online": true, "is_supporter": true, "last_visit": null,
+        "pm_friends_only": false, "profile_colour": null, "username": "mrekk"}}],
+    "spotlight": {"id": 270, "name": "October 2022", "type": "monthly",
+      "start_date": "2022-10-01T00:00:00+00:00", "end_date": "2022-10-31T00:00:00+00:00",
+      "mode_specific": true, "participant_count": 1234},
+    "total": 10000
+  }"#;
+  let rankings: Rankings = serde_json::from_str(resp).unwrap();
+  assert_eq!(rankings.cursor.unwrap().page, 2);
+  let entry = &rankings.ranking[0];
+  assert_eq!(entry.user.username, "mrekk");
+  assert_eq!(entry.statistics.global_rank, Some(1));
+  assert!(rankings.beatmapsets.is_empty());
+  let spotlight = rankings.spotlight.unwrap();
+  assert_eq!(spotlight.spotlight_type, SpotlightType::Monthly);
+  assert_eq!(spotlight.participant_count, Some(1234));
+
+  let resp = r#"{
+    "cursor": null,
+    "ranking": [{"code": "US", "active_users": 300000, "play_count": 999999,
+      "ranked_score": 12345678, "performance": 1234567.8,
+      "country": {"code": "US", "name": "United States"}}],
+    "total": 1
+  }"#;
+  let rankings: CountryRankings = serde_json::from_str(resp).unwrap();
+  assert!(rankings.cursor.is_none());
+  assert_eq!(rankings.ranking[0].country.name, "United States");
+}
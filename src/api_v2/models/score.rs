@@ -0,0 +1,347 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+use super::{de::ruleset_to_game_mode, Beatmap, Beatmapset, GameMode, User};
+use crate::{api_v2::Error, common::Query};
+
+/// Type of the user scores
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreType {
+  Best,
+  Recent,
+  Firsts,
+  Pinned,
+}
+
+impl ScoreType {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Best => "best",
+      Self::Recent => "recent",
+      Self::Firsts => "firsts",
+      Self::Pinned => "pinned",
+    }
+  }
+}
+
+#[derive(TypedBuilder, Debug, Clone)]
+#[builder(
+  builder_type_doc = "Builder for creating request to `/users/{user}/scores/{type}`,
+read https://osu.ppy.sh/docs/index.html#get-user-scores for meaning"
+)]
+pub struct GetUserScoresProp {
+  pub(crate) user_id: u64,
+  pub(crate) score_type: ScoreType,
+  /// Default to the user's favourite mode
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Include failed scores, only works with [`ScoreType::Recent`]
+  #[builder(setter(strip_bool))]
+  pub(crate) include_fails: bool,
+  /// Amount of results. Range between 1 and 100.
+  #[builder(default, setter(strip_option))]
+  pub(crate) limit: Option<u8>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) offset: Option<u32>,
+}
+
+impl TryFrom<GetUserScoresProp> for Query {
+  type Error = Error;
+
+  fn try_from(prop: GetUserScoresProp) -> Result<Self, Self::Error> {
+    let mut query = Query::new();
+
+    if prop.include_fails {
+      query.push("include_fails", 1);
+    }
+
+    if let Some(mode) = prop.mode {
+      query.push("mode", mode.ruleset());
+    }
+
+    if let Some(limit) = prop.limit {
+      if !(1..=100).contains(&limit) {
+        return Err(Error::InvalidRequestParams);
+      }
+      query.push("limit", limit);
+    }
+
+    if let Some(offset) = prop.offset {
+      query.push("offset", offset);
+    }
+
+    Ok(query)
+  }
+}
+
+/// Letter grade of a score, `XH` and `SH` are the silver SS and S
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grade {
+  XH,
+  X,
+  SH,
+  S,
+  A,
+  B,
+  C,
+  D,
+  F,
+}
+
+impl std::str::FromStr for Grade {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    use serde::de::Error as _;
+
+    let grade = match s {
+      "XH" => Self::XH,
+      "X" => Self::X,
+      "SH" => Self::SH,
+      "S" => Self::S,
+      "A" => Self::A,
+      "B" => Self::B,
+      "C" => Self::C,
+      "D" => Self::D,
+      "F" => Self::F,
+      _ => return Err(serde_json::Error::custom(format!("unknown grade {s}")).into()),
+    };
+
+    Ok(grade)
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ScoreStatistics {
+  pub count_300: u32,
+  pub count_100: u32,
+  pub count_50: u32,
+  // 激 (geki) in standard, max 300 in mania
+  pub count_geki: u32,
+  // 喝 (katu) in standard, 200 in mania
+  pub count_katu: u32,
+  pub count_miss: u32,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ScoreWeight {
+  // Percentage, like 95.0
+  pub percentage: f64,
+  // pp after weighting
+  pub pp: f64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Score {
+  // Null for scores converted from v1
+  pub id: Option<u64>,
+  pub user_id: u64,
+  // Between 0 and 1
+  pub accuracy: f64,
+  // Acronyms, like `["HD", "DT"]`. Use `ModsFlag::from_acronym` for the flags.
+  pub mods: Vec<String>,
+  pub score: u64,
+  pub max_combo: u32,
+  pub passed: bool,
+  // true = maximum combo of map reached, false otherwise
+  pub perfect: bool,
+  pub statistics: ScoreStatistics,
+  pub rank: Grade,
+  pub created_at: DateTime<Utc>,
+  // Null for beatmap that doesn't give pp, like loved map
+  pub pp: Option<f64>,
+  #[serde(deserialize_with = "ruleset_to_game_mode")]
+  pub mode: GameMode,
+  // If the replay is available or not
+  #[serde(default)]
+  pub replay: bool,
+  #[serde(default)]
+  pub beatmap: Option<Beatmap>,
+  #[serde(default)]
+  pub beatmapset: Option<Beatmapset>,
+  #[serde(default)]
+  pub user: Option<User>,
+  // Only returned by best scores
+  #[serde(default)]
+  pub weight: Option<ScoreWeight>,
+}
+
+impl ScoreStatistics {
+  /// Accuracy of the hits in the mode, between 0 and 1
+  pub fn accuracy(&self, mode: GameMode) -> f64 {
+    let [n300, n100, n50, geki, katu, miss] = [
+      self.count_300,
+      self.count_100,
+      self.count_50,
+      self.count_geki,
+      self.count_katu,
+      self.count_miss,
+    ]
+    .map(f64::from);
+
+    let (hit, total) = match mode {
+      GameMode::Standard => (
+        50.0 * n50 + 100.0 * n100 + 300.0 * n300,
+        300.0 * (n50 + n100 + n300 + miss),
+      ),
+      GameMode::Taiko => (0.5 * n100 + n300, n100 + n300 + miss),
+      GameMode::CatchTheBeat => (n50 + n100 + n300, n50 + n100 + n300 + katu + miss),
+      GameMode::Mania => (
+        50.0 * n50 + 100.0 * n100 + 200.0 * katu + 300.0 * (n300 + geki),
+        300.0 * (n50 + n100 + katu + n300 + geki + miss),
+      ),
+    };
+
+    if total == 0.0 {
+      0.0
+    } else {
+      hit / total
+    }
+  }
+}
+
+#[cfg(feature = "v1")]
+impl Score {
+  /// Convert a v1 recent score into v2 score. v1 doesn't tell the mode of the score, so it
+  /// must be the mode used in the request. Score id, pp and embedded objects are left empty.
+  ///
+  /// Fails with [`Error::UnexpectedResponse`] if the user id or the rank is malformed.
+  pub fn from_v1_recent(
+    score: crate::api_v1::GetUserRecentResp,
+    mode: GameMode,
+  ) -> Result<Self, Error> {
+    let statistics = ScoreStatistics {
+      count_300: score.count300,
+      count_100: score.count100,
+      count_50: score.count50,
+      count_geki: score.countgeki,
+      count_katu: score.countkatu,
+      count_miss: score.countmiss,
+    };
+    let rank: Grade = score.rank.parse()?;
+
+    Ok(Self {
+      id: None,
+      user_id: score.user_id.parse().map_err(|err| {
+        // Malformed id is a bad response, like the grade
+        <serde_json::Error as serde::de::Error>::custom(format!("invalid user id: {err}"))
+      })?,
+      accuracy: statistics.accuracy(mode),
+      mods: score
+        .enabled_mods
+        .acronyms()
+        .into_iter()
+        .map(String::from)
+        .collect(),
+      score: score.score,
+      max_combo: score.maxcombo,
+      passed: rank != Grade::F,
+      perfect: score.perfect,
+      statistics,
+      rank,
+      created_at: score.date,
+      pp: None,
+      mode,
+      replay: false,
+      beatmap: None,
+      beatmapset: None,
+      user: None,
+      weight: None,
+    })
+  }
+}
+
+#[test]
+fn test_deserialize_score() {
+  let resp = r#"[{
+    "accuracy": 0.9826, "best_id": 4012345678, "created_at": "2023-01-14T12:35:06+00:00",
+    "id": 4012345678, "max_combo": 788, "mode": "osu", "mode_int": 0, "mods": ["HD", "DT"],
+    "passed": true, "perfect": false, "pp": 412.338, "rank": "SH", "replay": true,
+    "score": 10483922, "statistics": {"count_100": 12, "count_300": 570, "count_50": 0,
+    "count_geki": 101, "count_katu": 9, "count_miss": 1}, "user_id": 11467817,
+    "beatmap": {"beatmapset_id": 896080, "difficulty_rating": 5.57, "id": 1872396,
+    "mode": "osu", "status": "ranked", "total_length": 154, "user_id": 1717160,
+    "version": "Extra", "accuracy": 8.8, "ar": 9.3, "bpm": 196, "convert": false,
+    "count_circles": 421, "count_sliders": 169, "count_spinners": 1, "cs": 4, "drain": 6,
+    "hit_length": 150, "is_scoreable": true, "last_updated": "2019-01-04T10:12:35+00:00",
+    "mode_int": 0, "passcount": 104220, "playcount": 812044, "ranked": 1,
+    "url": "https://osu.ppy.sh/beatmaps/1872396", "checksum": "1c5d2ff3bd3b2e07e6b5a8d6f2ef7b4f"},
+    "beatmapset": {"artist": "Yorushika", "artist_unicode": "ヨルシカ",
+    "covers": {"cover": "c", "card": "c", "list": "l", "slimcover": "s"},
+    "creator": "Log Off Now", "favourite_count": 871, "id": 896080, "nsfw": false,
+    "play_count": 1344197, "preview_url": "//b.ppy.sh/preview/896080.mp3", "source": "",
+    "status": "ranked", "title": "Tsukinami", "title_unicode": "ツキナミ",
+    "user_id": 1717160, "video": false},
+    "weight": {"percentage": 100, "pp": 412.338}
+  }]"#;
+  let scores: Vec<Score> = serde_json::from_str(resp).unwrap();
+  let score = &scores[0];
+  assert_eq!(score.rank, Grade::SH);
+  assert_eq!(score.mods, ["HD", "DT"]);
+  assert_eq!(score.statistics.count_100, 12);
+  assert_eq!(score.beatmap.as_ref().unwrap().bpm, Some(196.0));
+  assert_eq!(
+    score.beatmapset.as_ref().unwrap().status,
+    super::RankStatus::Ranked
+  );
+  assert_eq!(score.weight.as_ref().unwrap().percentage, 100.0);
+}
+
+#[cfg(feature = "v1")]
+#[test]
+fn test_from_v1_recent() {
+  let resp = r#"{"beatmap_id": "1872396", "score": "5402380", "maxcombo": "612",
+    "count50": "3", "count100": "41", "count300": "531", "countmiss": "4", "countkatu": "22",
+    "countgeki": "98", "perfect": "0", "enabled_mods": "584", "user_id": "11467817",
+    "date": "2023-01-14 12:35:06", "rank": "B"}"#;
+  let recent: crate::api_v1::GetUserRecentResp = serde_json::from_str(resp).unwrap();
+  let score = Score::from_v1_recent(recent, GameMode::Standard).unwrap();
+
+  assert_eq!(score.user_id, 11467817);
+  assert_eq!(score.rank, Grade::B);
+  assert!(score.passed);
+  // 584 = HD + NC
+  assert_eq!(score.mods, ["HD", "NC"]);
+  assert!((score.accuracy - 0.9416).abs() < 0.0001);
+
+  let resp = resp.replace(r#""user_id": "11467817""#, r#""user_id": "BlackDog5""#);
+  let recent: crate::api_v1::GetUserRecentResp = serde_json::from_str(&resp).unwrap();
+  assert!(matches!(
+    Score::from_v1_recent(recent, GameMode::Standard),
+    Err(Error::UnexpectedResponse(_))
+  ));
+
+  let resp = resp.replace(r#""user_id": "BlackDog5""#, r#""user_id": " 11467817""#);
+  let recent: crate::api_v1::GetUserRecentResp = serde_json::from_str(&resp).unwrap();
+  assert!(Score::from_v1_recent(recent, GameMode::Standard).is_err());
+
+  assert_eq!("XH".parse::<Grade>().unwrap(), Grade::XH);
+  assert!(matches!(
+    "SS".parse::<Grade>(),
+    Err(Error::UnexpectedResponse(_))
+  ));
+}
+
+#[test]
+fn test_user_scores_query() {
+  let prop = GetUserScoresProp::builder()
+    .user_id(2)
+    .score_type(ScoreType::Recent)
+    .mode(GameMode::Mania)
+    .include_fails()
+    .limit(5)
+    .build();
+  let query = Query::try_from(prop).unwrap();
+  assert_eq!(query.into_query_str(), "include_fails=1&mode=mania&limit=5");
+
+  let prop = GetUserScoresProp::builder()
+    .user_id(2)
+    .score_type(ScoreType::Best)
+    .limit(101)
+    .build();
+  assert!(matches!(
+    Query::try_from(prop),
+    Err(Error::InvalidRequestParams)
+  ));
+}
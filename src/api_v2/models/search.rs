@@ -0,0 +1,218 @@
+use serde::Deserialize;
+use typed_builder::TypedBuilder;
+
+use super::{Beatmapset, GameMode, Genre, Language};
+use crate::common::Query;
+
+/// Ranked status filter of the beatmapset search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStatus {
+  Any,
+  Ranked,
+  Loved,
+  Qualified,
+  Pending,
+  Graveyard,
+}
+
+impl SearchStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Any => "any",
+      Self::Ranked => "ranked",
+      Self::Loved => "loved",
+      Self::Qualified => "qualified",
+      Self::Pending => "pending",
+      Self::Graveyard => "graveyard",
+    }
+  }
+}
+
+/// Field to sort the search results by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+  Title,
+  Artist,
+  Difficulty,
+  Ranked,
+  Rating,
+  Plays,
+  Favourites,
+  Relevance,
+}
+
+impl SearchSort {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Title => "title",
+      Self::Artist => "artist",
+      Self::Difficulty => "difficulty",
+      Self::Ranked => "ranked",
+      Self::Rating => "rating",
+      Self::Plays => "plays",
+      Self::Favourites => "favourites",
+      Self::Relevance => "relevance",
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+  Asc,
+  Desc,
+}
+
+#[derive(TypedBuilder, Debug, Clone)]
+#[builder(builder_type_doc = "Builder for creating request to `/beatmapsets/search`")]
+pub struct SearchBeatmapsetsProp {
+  /// Free text to search in title, artist, creator, tags and so on
+  #[builder(default, setter(into, strip_option))]
+  pub(crate) query: Option<String>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) mode: Option<GameMode>,
+  /// Default to the beatmapsets with leaderboard, which are ranked, approved, qualified and
+  /// loved
+  #[builder(default, setter(strip_option))]
+  pub(crate) status: Option<SearchStatus>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) genre: Option<Genre>,
+  #[builder(default, setter(strip_option))]
+  pub(crate) language: Option<Language>,
+  /// Only return beatmapsets with video
+  #[builder(setter(strip_bool))]
+  pub(crate) video: bool,
+  /// Only return beatmapsets with storyboard
+  #[builder(setter(strip_bool))]
+  pub(crate) storyboard: bool,
+  /// Default to relevance when searching text, or ranked date otherwise
+  #[builder(default, setter(strip_option))]
+  pub(crate) sort: Option<SearchSort>,
+  #[builder(default = SortDirection::Desc)]
+  pub(crate) direction: SortDirection,
+  /// Include beatmapsets marked as explicit content
+  #[builder(setter(strip_bool))]
+  pub(crate) nsfw: bool,
+  /// Cursor of the page, from [`BeatmapsetSearchResp::cursor_string`] of the previous page
+  #[builder(default, setter(into, strip_option))]
+  pub(crate) cursor_string: Option<String>,
+}
+
+impl SearchBeatmapsetsProp {
+  /// Same search for the page of the cursor
+  pub fn with_cursor(mut self, cursor_string: impl Into<String>) -> Self {
+    self.cursor_string = Some(cursor_string.into());
+    self
+  }
+}
+
+impl From<&SearchBeatmapsetsProp> for Query {
+  fn from(prop: &SearchBeatmapsetsProp) -> Self {
+    let mut query = Query::new();
+
+    if let Some(text) = &prop.query {
+      query.push("q", text);
+    }
+
+    if let Some(mode) = prop.mode {
+      query.push("m", mode);
+    }
+
+    if let Some(status) = prop.status {
+      query.push("s", status.as_str());
+    }
+
+    if let Some(genre) = prop.genre {
+      query.push("g", genre);
+    }
+
+    if let Some(language) = prop.language {
+      query.push("l", language);
+    }
+
+    let extra: Vec<_> = [(prop.video, "video"), (prop.storyboard, "storyboard")]
+      .into_iter()
+      .filter_map(|(enabled, name)| enabled.then_some(name))
+      .collect();
+    if !extra.is_empty() {
+      query.push("e", extra.join("."));
+    }
+
+    if let Some(sort) = prop.sort {
+      let direction = match prop.direction {
+        SortDirection::Asc => "asc",
+        SortDirection::Desc => "desc",
+      };
+      query.push("sort", format!("{}_{direction}", sort.as_str()));
+    }
+
+    if prop.nsfw {
+      query.push("nsfw", true);
+    }
+
+    if let Some(cursor) = &prop.cursor_string {
+      query.push("cursor_string", cursor);
+    }
+
+    query
+  }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BeatmapsetSearchResp {
+  pub beatmapsets: Vec<Beatmapset>,
+  // Cursor of the next page, null if this is the last page
+  #[serde(default)]
+  pub cursor_string: Option<String>,
+  // Count of all the matched beatmapsets
+  pub total: u32,
+}
+
+#[test]
+fn test_search_query() {
+  let prop = SearchBeatmapsetsProp::builder()
+    .query("tsukinami")
+    .mode(GameMode::Standard)
+    .status(SearchStatus::Loved)
+    .genre(Genre::Pop)
+    .language(Language::Japanese)
+    .video()
+    .storyboard()
+    .sort(SearchSort::Plays)
+    .nsfw()
+    .cursor_string("eyJfc2NvcmUiOjE")
+    .build();
+  assert_eq!(
+    Query::from(&prop).into_query_str(),
+    "q=tsukinami&m=0&s=loved&g=5&l=3&e=video.storyboard&sort=plays_desc&nsfw=true\
+    &cursor_string=eyJfc2NvcmUiOjE"
+  );
+
+  let prop = SearchBeatmapsetsProp::builder().build();
+  assert!(Query::from(&prop).pairs().is_empty());
+  let prop = prop.with_cursor("next");
+  assert_eq!(Query::from(&prop).into_query_str(), "cursor_string=next");
+}
+
+#[test]
+fn test_deserialize_search() {
+  let resp = r#"{
+    "beatmapsets": [{"artist": "Yorushika", "artist_unicode": "ヨルシカ",
+    "covers": {"cover": "c", "card": "c", "list": "l", "slimcover": "s"},
+    "creator": "Log Off Now", "favourite_count": 871, "id": 896080, "nsfw": false,
+    "play_count": 1344197, "preview_url": "//b.ppy.sh/preview/896080.mp3", "source": "",
+    "status": "ranked", "title": "Tsukinami", "title_unicode": "ツキナミ",
+    "user_id": 1717160, "video": false, "bpm": 196, "tags": "ツキナミ n-buna suis",
+    "ranked_date": "2019-01-12T00:00:57+00:00", "submitted_date": "2018-12-03T14:29:44+00:00",
+    "last_updated": "2019-01-04T10:12:35+00:00", "storyboard": false,
+    "beatmaps": [{"beatmapset_id": 896080, "difficulty_rating": 5.57, "id": 1872396,
+    "mode": "osu", "status": "ranked", "total_length": 154, "user_id": 1717160,
+    "version": "Extra"}]}],
+    "search": {"sort": "plays_desc"}, "recommended_difficulty": null, "error": null,
+    "total": 4321, "cursor": {"play_count": 1344197, "id": 896080},
+    "cursor_string": "eyJwbGF5X2NvdW50IjoxMzQ0MTk3LCJpZCI6ODk2MDgwfQ"
+  }"#;
+  let resp: BeatmapsetSearchResp = serde_json::from_str(resp).unwrap();
+  assert_eq!(resp.total, 4321);
+  assert!(resp.cursor_string.is_some());
+  assert_eq!(resp.beatmapsets[0].beatmaps[0].id, 1872396);
+}
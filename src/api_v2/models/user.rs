@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{
+  de::{ruleset_to_game_mode, ruleset_to_statistics},
+  GameMode,
+};
+
+/// User object returned by most of the endpoints. Optional attributes are only returned by some
+/// of the endpoints.
+#[derive(Deserialize, Debug, Clone)]
+pub struct User {
+  pub id: u64,
+  pub username: String,
+  pub avatar_url: String,
+  // two letters ISO 3166-1 alpha-2 code
+  pub country_code: String,
+  pub default_group: Option<String>,
+  pub is_active: bool,
+  pub is_bot: bool,
+  pub is_deleted: bool,
+  pub is_online: bool,
+  pub is_supporter: bool,
+  // Null if the user hides online status
+  pub last_visit: Option<DateTime<Utc>>,
+  pub pm_friends_only: bool,
+  // hex color code like `#ff66aa`
+  pub profile_colour: Option<String>,
+  #[serde(default)]
+  pub country: Option<Country>,
+  #[serde(default)]
+  pub cover: Option<UserCover>,
+  #[serde(default)]
+  pub statistics: Option<UserStatistics>,
+  // Statistics of every ruleset, only returned by the `/users` lookup
+  #[serde(default, deserialize_with = "ruleset_to_statistics")]
+  pub statistics_rulesets: HashMap<GameMode, UserStatistics>,
+  #[serde(default)]
+  pub rank_history: Option<RankHistory>,
+  #[serde(default)]
+  pub badges: Vec<UserBadge>,
+}
+
+/// User object with profile details, returned by [`OsuClient::get_user`](crate::api_v2::OsuClient::get_user)
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserExtended {
+  #[serde(flatten)]
+  pub user: User,
+  pub join_date: DateTime<Utc>,
+  // favourite mode of the user
+  #[serde(deserialize_with = "ruleset_to_game_mode")]
+  pub playmode: GameMode,
+  // devices the user plays with, like `mouse` and `tablet`
+  #[serde(default)]
+  pub playstyle: Option<Vec<String>>,
+  pub post_count: u32,
+  pub has_supported: bool,
+  pub title: Option<String>,
+  pub location: Option<String>,
+  pub interests: Option<String>,
+  pub occupation: Option<String>,
+  pub twitter: Option<String>,
+  pub discord: Option<String>,
+  pub website: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Country {
+  pub code: String,
+  pub name: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserCover {
+  pub custom_url: Option<String>,
+  pub url: String,
+  // Null if the cover is uploaded by the user
+  pub id: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserBadge {
+  pub awarded_at: DateTime<Utc>,
+  pub description: String,
+  pub image_url: String,
+  pub url: String,
+}
+
+/// Global rank of the last 90 days, from the oldest
+#[derive(Deserialize, Debug, Clone)]
+pub struct RankHistory {
+  #[serde(deserialize_with = "ruleset_to_game_mode")]
+  pub mode: GameMode,
+  pub data: Vec<u32>,
+}
+
+/// Statistics of the user in a mode
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserStatistics {
+  pub count_300: u64,
+  pub count_100: u64,
+  pub count_50: u64,
+  pub count_miss: u64,
+  pub level: UserLevel,
+  // Null if the user is inactive or has no ranked play
+  pub global_rank: Option<u32>,
+  #[serde(default)]
+  pub country_rank: Option<u32>,
+  pub pp: f64,
+  pub ranked_score: u64,
+  // Percentage, like 98.5
+  pub hit_accuracy: f64,
+  pub play_count: u32,
+  // In seconds
+  #[serde(default)]
+  pub play_time: Option<u64>,
+  pub total_score: u64,
+  pub total_hits: u64,
+  pub maximum_combo: u32,
+  pub replays_watched_by_others: u32,
+  pub is_ranked: bool,
+  pub grade_counts: GradeCounts,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UserLevel {
+  pub current: u32,
+  // Percentage to the next level
+  pub progress: u32,
+}
+
+/// Count of the ranks the user achieved, `ssh` and `sh` are the silver SS and S
+#[derive(Deserialize, Debug, Clone)]
+pub struct GradeCounts {
+  pub ss: i32,
+  pub ssh: i32,
+  pub s: i32,
+  pub sh: i32,
+  pub a: i32,
+}
+
+/// Response of the `/users` lookup
+#[derive(Deserialize)]
+pub(crate) struct GetUsersResp {
+  pub(crate) users: Vec<User>,
+}
+
+#[test]
+fn test_deserialize_user() {
+  let resp = r##"{
+    "avatar_url": "https://a.ppy.sh/2?1657169614.jpeg", "country_code": "AU",
+    "default_group": "default", "id": 2, "is_active": true, "is_bot": false,
+    "is_deleted": false, "is_online": false, "is_supporter": true,
+    "last_visit": "2023-01-15T10:23:58+00:00", "pm_friends_only": false,
+    "profile_colour": "#3366FF", "username": "peppy", "cover_url": "https://assets.ppy.sh/cover.jpg",
+    "discord": null, "has_supported": true, "interests": null,
+    "join_date": "2007-08-28T03:09:12+00:00", "location": "Tokyo", "max_blocks": 100,
+    "max_friends": 500, "occupation": null, "playmode": "osu", "playstyle": ["mouse", "keyboard"],
+    "post_count": 45000, "title": null, "twitter": "ppy", "website": "https://ppy.sh",
+    "country": {"code": "AU", "name": "Australia"},
+    "cover": {"custom_url": "https://assets.ppy.sh/cover.jpg", "url": "https://assets.ppy.sh/cover.jpg", "id": null},
+    "badges": [{"awarded_at": "2014-07-08T06:18:04+00:00", "description": "Mapping contest winner",
+      "image_url": "https://assets.ppy.sh/badge.png", "url": ""}],
+    "statistics": {
+      "count_100": 76016, "count_300": 640510, "count_50": 15426, "count_miss": 27413,
+      "level": {"current": 66, "progress": 40}, "global_rank": 542001, "country_rank": 12345,
+      "pp": 1023.5, "ranked_score": 471372543, "hit_accuracy": 93.1452, "play_count": 5411,
+      "play_time": 392415, "total_score": 1285302012, "total_hits": 731952,
+      "maximum_combo": 1338, "replays_watched_by_others": 54232, "is_ranked": true,
+      "grade_counts": {"ss": 12, "ssh": 3, "s": 144, "sh": 5, "a": 354}
+    },
+    "rank_history": {"mode": "osu", "data": [542100, 542050, 542001]}
+  }"##;
+  let user: UserExtended = serde_json::from_str(resp).unwrap();
+  assert_eq!(user.user.id, 2);
+  assert_eq!(user.playmode, GameMode::Standard);
+  assert!(user.discord.is_none());
+
+  let stats = user.user.statistics.unwrap();
+  assert_eq!(stats.global_rank, Some(542001));
+  assert_eq!(stats.grade_counts.ssh, 3);
+  assert_eq!(user.user.rank_history.unwrap().data.len(), 3);
+  assert_eq!(user.user.badges[0].description, "Mapping contest winner");
+}
+
+#[test]
+fn test_deserialize_users_lookup() {
+  let resp = r##"{"users": [{
+    "avatar_url": "https://a.ppy.sh/2?1657169614.jpeg", "country_code": "AU",
+    "default_group": "default", "id": 2, "is_active": true, "is_bot": false,
+    "is_deleted": false, "is_online": false, "is_supporter": true,
+    "last_visit": "2023-01-15T10:23:58+00:00", "pm_friends_only": false,
+    "profile_colour": "#3366FF", "username": "peppy",
+    "country": {"code": "AU", "name": "Australia"},
+    "cover": {"custom_url": null, "url": "https://assets.ppy.sh/cover.jpg", "id": "4"},
+    "groups": [],
+    "statistics_rulesets": {
+      "osu": {
+        "count_100": 76016, "count_300": 640510, "count_50": 15426, "count_miss": 27413,
+        "level": {"current": 66, "progress": 40}, "global_rank": 542001, "pp": 1023.5,
+        "ranked_score": 471372543, "hit_accuracy": 93.1452, "play_count": 5411,
+        "play_time": 392415, "total_score": 1285302012, "total_hits": 731952,
+        "maximum_combo": 1338, "replays_watched_by_others": 54232, "is_ranked": true,
+        "grade_counts": {"ss": 12, "ssh": 3, "s": 144, "sh": 5, "a": 354}
+      },
+      "taiko": {
+        "count_100": 120, "count_300": 900, "count_50": 0, "count_miss": 31,
+        "level": {"current": 12, "progress": 5}, "global_rank": null, "pp": 0,
+        "ranked_score": 1200000, "hit_accuracy": 91.2, "play_count": 14,
+        "play_time": 2100, "total_score": 2400000, "total_hits": 1020,
+        "maximum_combo": 402, "replays_watched_by_others": 0, "is_ranked": false,
+        "grade_counts": {"ss": 0, "ssh": 0, "s": 1, "sh": 0, "a": 3}
+      },
+      "fruits": null
+    }
+  }]}"##;
+  let resp: GetUsersResp = serde_json::from_str(resp).unwrap();
+  let user = &resp.users[0];
+  assert!(user.statistics.is_none());
+  assert_eq!(user.statistics_rulesets.len(), 2);
+  assert_eq!(
+    user.statistics_rulesets[&GameMode::Standard].global_rank,
+    Some(542001)
+  );
+  assert!(!user.statistics_rulesets[&GameMode::Taiko].is_ranked);
+  assert!(!user
+    .statistics_rulesets
+    .contains_key(&GameMode::CatchTheBeat));
+}
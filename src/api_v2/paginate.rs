@@ -0,0 +1,370 @@
+use std::{
+  collections::VecDeque,
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll},
+};
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+
+use crate::api_v2::{
+  Beatmapset, CountryRanking, Error, GameMode, GetRankingsProp, GetUserScoresProp, OsuClient,
+  Score, SearchBeatmapsetsProp, UserRanking,
+};
+
+/// Position of a page in the paginated endpoints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Page {
+  /// The `cursor_string` returned by the previous page
+  Cursor(String),
+  /// Count of items to skip
+  Offset(u32),
+  /// Page number, starting from 1
+  Number(u32),
+}
+
+enum Next {
+  First,
+  Page(Page),
+  Done,
+}
+
+struct State<T, F> {
+  fetch: F,
+  buffer: VecDeque<T>,
+  next: Next,
+}
+
+/// A stream of the items of a paginated endpoint. Pages are fetched only when the items of
+/// the previous page are consumed, and the stream ends after the last page or the first error.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), osu_api::api_v2::Error> {
+/// use futures::TryStreamExt;
+/// use osu_api::api_v2::{OsuClient, SearchBeatmapsetsProp};
+///
+/// let client = OsuClient::new(1234, "YOUR_CLIENT_SECRET");
+/// let prop = SearchBeatmapsetsProp::builder().query("tsukinami").build();
+/// let sets: Vec<_> = client
+///   .search_beatmapsets_stream(prop)
+///   .max_items(100)
+///   .try_collect()
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<'a, T> {
+  inner: BoxStream<'a, Result<T, Error>>,
+}
+
+impl<'a, T: Send + 'a> Paginator<'a, T> {
+  /// Create a paginator from the function that fetches the page. The function is called with
+  /// `None` for the first page, and returns the items along with the position of the next page,
+  /// which is `None` if there is no more page.
+  pub fn new<F, Fut>(fetch: F) -> Self
+  where
+    F: FnMut(Option<Page>) -> Fut + Send + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Option<Page>), Error>> + Send + 'a,
+  {
+    let state = State {
+      fetch,
+      buffer: VecDeque::new(),
+      next: Next::First,
+    };
+
+    let inner = futures::stream::unfold(state, |mut state| async move {
+      loop {
+        if let Some(item) = state.buffer.pop_front() {
+          return Some((Ok(item), state));
+        }
+
+        let page = match std::mem::replace(&mut state.next, Next::Done) {
+          Next::First => None,
+          Next::Page(page) => Some(page),
+          Next::Done => return None,
+        };
+        match (state.fetch)(page).await {
+          // An empty page ends the stream, even if it comes with a cursor
+          Ok((items, next)) if !items.is_empty() => {
+            state.buffer.extend(items);
+            state.next = next.map_or(Next::Done, Next::Page);
+          }
+          Ok(_) => return None,
+          Err(err) => return Some((Err(err), state)),
+        }
+      }
+    });
+
+    Self {
+      inner: inner.boxed(),
+    }
+  }
+
+  /// Stop after the given count of items, pages after it won't be fetched
+  pub fn max_items(self, count: usize) -> Self {
+    Self {
+      inner: self.inner.take(count).boxed(),
+    }
+  }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+  type Item = Result<T, Error>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    self.inner.as_mut().poll_next(cx)
+  }
+}
+
+impl OsuClient {
+  /// Search beatmapsets and go through all the pages
+  pub fn search_beatmapsets_stream(
+    &self,
+    prop: SearchBeatmapsetsProp,
+  ) -> Paginator<'_, Beatmapset> {
+    Paginator::new(move |page| {
+      let prop = match page {
+        Some(Page::Cursor(cursor)) => prop.clone().with_cursor(cursor),
+        _ => prop.clone(),
+      };
+      async move {
+        let resp = self.search_beatmapsets(&prop).await?;
+        Ok((resp.beatmapsets, resp.cursor_string.map(Page::Cursor)))
+      }
+    })
+  }
+
+  /// Get the scores of the user and go through all the pages. The limit of the prop is used as
+  /// the page size, default to 100.
+  pub fn user_scores_stream(&self, prop: GetUserScoresProp) -> Paginator<'_, Score> {
+    let page_size = prop.limit.unwrap_or(100);
+    let start = prop.offset.unwrap_or(0);
+
+    Paginator::new(move |page| {
+      let offset = match page {
+        Some(Page::Offset(offset)) => offset,
+        _ => start,
+      };
+      let prop = GetUserScoresProp {
+        limit: Some(page_size),
+        offset: Some(offset),
+        ..prop.clone()
+      };
+      async move {
+        let scores = self.get_user_scores(prop).await?;
+        // A page shorter than the page size is the last one
+        let next = (scores.len() >= usize::from(page_size))
+          .then(|| Page::Offset(offset + scores.len() as u32));
+        Ok((scores, next))
+      }
+    })
+  }
+
+  /// Get the user rankings and go through all the pages, starting from the page of the prop
+  pub fn rankings_stream(&self, prop: GetRankingsProp) -> Paginator<'_, UserRanking> {
+    Paginator::new(move |page| {
+      let prop = match page {
+        Some(Page::Number(number)) => GetRankingsProp {
+          page: Some(number),
+          ..prop.clone()
+        },
+        _ => prop.clone(),
+      };
+      async move {
+        let resp = self.get_rankings(&prop).await?;
+        let next = resp.cursor.map(|cursor| Page::Number(cursor.page));
+        Ok((resp.ranking, next))
+      }
+    })
+  }
+
+  /// Get the country rankings and go through all the pages
+  pub fn country_rankings_stream(&self, mode: GameMode) -> Paginator<'_, CountryRanking> {
+    Paginator::new(move |page| {
+      let number = match page {
+        Some(Page::Number(number)) => Some(number),
+        _ => None,
+      };
+      async move {
+        let resp = self.get_country_rankings(mode, number).await?;
+        let next = resp.cursor.map(|cursor| Page::Number(cursor.page));
+        Ok((resp.ranking, next))
+      }
+    })
+  }
+}
+
+#[tokio::test]
+async fn test_paginator() {
+  use futures::TryStreamExt;
+  use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+  };
+
+  // 3 pages of 2 items, then an empty page
+  let fetched = Arc::new(AtomicU32::new(0));
+  let paginator = |fetched: Arc<AtomicU32>| {
+    Paginator::new(move |page| {
+      fetched.fetch_add(1, Ordering::SeqCst);
+      async move {
+        let offset = match page {
+          None => 0,
+          Some(Page::Offset(offset)) => offset,
+          Some(page) => panic!("unexpected page {page:?}"),
+        };
+        let items: Vec<u32> = if offset < 6 {
+          vec![offset, offset + 1]
+        } else {
+          Vec::new()
+        };
+        Ok((items, Some(Page::Offset(offset + 2))))
+      }
+    })
+  };
+
+  let items: Vec<u32> = paginator(fetched.clone()).try_collect().await.unwrap();
+  assert_eq!(items, [0, 1, 2, 3, 4, 5]);
+  assert_eq!(fetched.load(Ordering::SeqCst), 4);
+
+  // The third page is never fetched
+  fetched.store(0, Ordering::SeqCst);
+  let items: Vec<u32> = paginator(fetched.clone())
+    .max_items(3)
+    .try_collect()
+    .await
+    .unwrap();
+  assert_eq!(items, [0, 1, 2]);
+  assert_eq!(fetched.load(Ordering::SeqCst), 2);
+
+  // Stop after error
+  let mut errors = Paginator::<u32>::new(|_| async { Err(Error::NotFound) });
+  assert!(matches!(errors.next().await, Some(Err(Error::NotFound))));
+  assert!(errors.next().await.is_none());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_search_beatmapsets_stream() {
+  use crate::{
+    ratelimit::RateLimiter,
+    stub::{stub_v2_server, StubResponse},
+  };
+  use futures::TryStreamExt;
+  use std::{sync::Arc, time::Duration};
+  use tokio::time::Instant;
+
+  let beatmapset = |id: u64| {
+    format!(
+      r#"{{"artist": "a", "artist_unicode": "a", "covers": {{"cover": "c", "card": "c",
+      "list": "l", "slimcover": "s"}}, "creator": "c", "favourite_count": 0, "id": {id},
+      "nsfw": false, "play_count": 0, "preview_url": "p", "source": "", "status": "ranked",
+      "title": "t", "title_unicode": "t", "user_id": 1, "video": false}}"#
+    )
+  };
+  let server = stub_v2_server(move |req| {
+    let body = if req.path.contains("cursor_string=page2") {
+      format!(
+        r#"{{"beatmapsets": [{}], "cursor_string": null, "total": 3}}"#,
+        beatmapset(3)
+      )
+    } else {
+      format!(
+        r#"{{"beatmapsets": [{}, {}], "cursor_string": "page2", "total": 3}}"#,
+        beatmapset(1),
+        beatmapset(2)
+      )
+    };
+    StubResponse::json(200, body)
+  })
+  .await;
+  // The bucket starts full, so the n-th request is allowed n periods after the limiter is created
+  let period = Duration::from_millis(100);
+  let start = Instant::now();
+  let client = OsuClient::builder()
+    .client_id(1234)
+    .client_secret("secret")
+    .base_url(server.url("/"))
+    .limiter(Arc::new(RateLimiter::new(1, period)))
+    .build();
+
+  let prop = SearchBeatmapsetsProp::builder().query("tsukinami").build();
+  let sets: Vec<_> = client
+    .search_beatmapsets_stream(prop)
+    .try_collect()
+    .await
+    .unwrap();
+  let ids: Vec<_> = sets.iter().map(|set| set.id).collect();
+  assert_eq!(ids, [1, 2, 3]);
+
+  let pages: Vec<_> = server
+    .requests()
+    .into_iter()
+    .filter(|req| req.path.starts_with("/api/v2/beatmapsets/search"))
+    .map(|req| req.path)
+    .collect();
+  assert_eq!(
+    pages,
+    [
+      "/api/v2/beatmapsets/search?q=tsukinami",
+      "/api/v2/beatmapsets/search?q=tsukinami&cursor_string=page2"
+    ]
+  );
+
+  // One request per period, the token request counts, and the later pages wait for the limiter
+  let requests = server.requests();
+  assert_eq!(requests.len(), 3);
+  for (n, req) in requests.iter().enumerate() {
+    assert!(req.received_at - start >= period * n as u32);
+  }
+}
+
+#[tokio::test]
+async fn test_country_rankings_stream() {
+  use crate::stub::{stub_v2_client, StubResponse};
+  use futures::TryStreamExt;
+
+  let country = |code: &str| {
+    format!(
+      r#"{{"code": "{code}", "active_users": 1, "play_count": 1, "ranked_score": 1,
+      "performance": 1.0, "country": {{"code": "{code}", "name": "{code}"}}}}"#
+    )
+  };
+  let (server, client) = stub_v2_client(move |req| {
+    let body = if req.path.ends_with("cursor%5Bpage%5D=2") {
+      format!(
+        r#"{{"cursor": null, "ranking": [{}], "total": 3}}"#,
+        country("JP")
+      )
+    } else {
+      format!(
+        r#"{{"cursor": {{"page": 2}}, "ranking": [{}, {}], "total": 3}}"#,
+        country("US"),
+        country("KR")
+      )
+    };
+    StubResponse::json(200, body)
+  })
+  .await;
+
+  let countries: Vec<_> = client
+    .country_rankings_stream(GameMode::Taiko)
+    .try_collect()
+    .await
+    .unwrap();
+  let codes: Vec<_> = countries.iter().map(|c| c.code.as_str()).collect();
+  assert_eq!(codes, ["US", "KR", "JP"]);
+
+  let pages: Vec<_> = server
+    .requests()
+    .into_iter()
+    .filter(|req| req.path.starts_with("/api/v2/rankings"))
+    .map(|req| req.path)
+    .collect();
+  assert_eq!(
+    pages,
+    [
+      "/api/v2/rankings/taiko/country",
+      "/api/v2/rankings/taiko/country?cursor%5Bpage%5D=2"
+    ]
+  );
+}
@@ -0,0 +1,349 @@
+//! Types shared by both versions of the API
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Key value pairs of the request query string. Keys and values are kept
+/// as is, and are percent-encoded when building the query string.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Query {
+  pair: Vec<(String, String)>,
+}
+
+impl Query {
+  pub fn new() -> Self {
+    Self { pair: Vec::new() }
+  }
+
+  pub fn push(&mut self, key: impl Display, val: impl Display) {
+    self.pair.push((key.to_string(), val.to_string()))
+  }
+
+  /// Inspect the pairs pushed into this query, in pushing order.
+  pub fn pairs(&self) -> &[(String, String)] {
+    &self.pair
+  }
+
+  pub fn into_query_str(self) -> String {
+    url::form_urlencoded::Serializer::new(String::new())
+      .extend_pairs(self.pair)
+      .finish()
+  }
+}
+
+/// Delay to wait from the `Retry-After` response header. Only the delay-seconds form is
+/// supported.
+#[cfg(any(feature = "v1", feature = "v2"))]
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+  headers
+    .get(reqwest::header::RETRY_AFTER)
+    .and_then(|val| val.to_str().ok()?.parse().ok())
+    .map(std::time::Duration::from_secs)
+}
+
+/// Error object the API respond with, like `{"error": "invalid cursor"}`
+#[cfg(any(feature = "v1", feature = "v2"))]
+#[derive(Deserialize)]
+struct ErrorBody {
+  error: String,
+}
+
+/// Message of the error object in the response body, if the body is one
+#[cfg(any(feature = "v1", feature = "v2"))]
+pub(crate) fn error_message(body: &[u8]) -> Option<String> {
+  serde_json::from_slice::<ErrorBody>(body)
+    .ok()
+    .map(|body| body.error)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameMode {
+  Standard,
+  Taiko,
+  CatchTheBeat,
+  Mania,
+}
+
+impl GameMode {
+  /// Name of the ruleset used by the v2 API, like `osu` and `fruits`
+  pub fn ruleset(&self) -> &'static str {
+    match self {
+      Self::Standard => "osu",
+      Self::Taiko => "taiko",
+      Self::CatchTheBeat => "fruits",
+      Self::Mania => "mania",
+    }
+  }
+
+  pub fn from_ruleset(name: &str) -> Option<Self> {
+    match name {
+      "osu" => Some(Self::Standard),
+      "taiko" => Some(Self::Taiko),
+      "fruits" => Some(Self::CatchTheBeat),
+      "mania" => Some(Self::Mania),
+      _ => None,
+    }
+  }
+}
+
+// Display the numeric value used by the v1 API
+impl std::fmt::Display for GameMode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.as_ref())
+  }
+}
+
+impl AsRef<str> for GameMode {
+  fn as_ref(&self) -> &str {
+    use GameMode::*;
+
+    match self {
+      Standard => "0",
+      Taiko => "1",
+      CatchTheBeat => "2",
+      Mania => "3",
+    }
+  }
+}
+
+/// Genre of the beatmapset, the value is the id used by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Genre {
+  Any = 0,
+  Unspecified = 1,
+  VideoGame = 2,
+  Anime = 3,
+  Rock = 4,
+  Pop = 5,
+  Other = 6,
+  Novelty = 7,
+  HipHop = 9,
+  Electronic = 10,
+  Metal = 11,
+  Classical = 12,
+  Folk = 13,
+  Jazz = 14,
+}
+
+/// Language of the beatmapset, the value is the id used by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+  Any = 0,
+  Unspecified = 1,
+  English = 2,
+  Japanese = 3,
+  Chinese = 4,
+  Instrumental = 5,
+  Korean = 6,
+  French = 7,
+  German = 8,
+  Swedish = 9,
+  Spanish = 10,
+  Italian = 11,
+  Russian = 12,
+  Polish = 13,
+  Other = 14,
+}
+
+// Display the id used by the API
+impl std::fmt::Display for Genre {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", *self as u8)
+  }
+}
+
+impl std::fmt::Display for Language {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", *self as u8)
+  }
+}
+
+#[derive(Debug)]
+pub enum UserId<'u> {
+  Id(u64),
+  Username(&'u str),
+}
+
+impl From<u64> for UserId<'_> {
+  fn from(id: u64) -> Self {
+    Self::Id(id)
+  }
+}
+
+impl<'a> From<&'a str> for UserId<'a> {
+  fn from(name: &'a str) -> Self {
+    Self::Username(name)
+  }
+}
+
+bitflags::bitflags! {
+  pub struct ModsFlag: u64 {
+    const NONE              = 0;
+    const NOFAIL            = 1;
+    const EASY              = 2;
+    const TOUCHDEVICE       = 4;
+    const HIDDEN            = 8;
+    const HARDROCK          = 16;
+    const SUDDENDEATH       = 32;
+    const DOUBLETIME        = 64;
+    const RELAX             = 128;
+    const HALFTIME          = 256;
+    const NIGHTCORE         = 512 | Self::DOUBLETIME.bits;
+    const FLASHLIGHT        = 1024;
+    const AUTOPLAY          = 2048;
+    const SPUNOUT           = 4096;
+    const RELAX2            = 8192;    // Autopilot
+    const PERFECT           = 16384 | Self::SUDDENDEATH.bits;
+    const KEY4              = 32768;
+    const KEY5              = 65536;
+    const KEY6              = 131072;
+    const KEY7              = 262144;
+    const KEY8              = 524288;
+    const FADEIN            = 1048576;
+    const RANDOM            = 2097152;
+    const CINEMA            = 4194304;
+    const TARGET            = 8388608;
+    const KEY9              = 16777216;
+    const KEYCOOP           = 33554432;
+    const KEY1              = 67108864;
+    const KEY3              = 134217728;
+    const KEY2              = 268435456;
+    const SCOREV2           = 536870912;
+    const MIRROR            = 1073741824;
+    const KEYMOD            = Self::KEY1.bits
+                            | Self::KEY2.bits
+                            | Self::KEY3.bits
+                            | Self::KEY4.bits
+                            | Self::KEY5.bits
+                            | Self::KEY6.bits
+                            | Self::KEY7.bits
+                            | Self::KEY8.bits
+                            | Self::KEY9.bits
+                            | Self::KEYCOOP.bits;
+    const FREEMODALLOWED    = Self::NOFAIL.bits
+                            | Self::EASY.bits
+                            | Self::HIDDEN.bits
+                            | Self::HARDROCK.bits
+                            | Self::SUDDENDEATH.bits
+                            | Self::FLASHLIGHT.bits
+                            | Self::FADEIN.bits
+                            | Self::RELAX.bits
+                            | Self::RELAX2.bits
+                            | Self::SPUNOUT.bits
+                            | Self::KEYMOD.bits;
+    const SCOREINCREASEMODS = Self::HIDDEN.bits
+                            | Self::HARDROCK.bits
+                            | Self::DOUBLETIME.bits
+                            | Self::FLASHLIGHT.bits
+                            | Self::FADEIN.bits;
+  }
+}
+
+// Acronyms used by the v2 API, in the order of the bits
+const MODS_ACRONYMS: [(ModsFlag, &str); 31] = [
+  (ModsFlag::NOFAIL, "NF"),
+  (ModsFlag::EASY, "EZ"),
+  (ModsFlag::TOUCHDEVICE, "TD"),
+  (ModsFlag::HIDDEN, "HD"),
+  (ModsFlag::HARDROCK, "HR"),
+  (ModsFlag::SUDDENDEATH, "SD"),
+  (ModsFlag::DOUBLETIME, "DT"),
+  (ModsFlag::RELAX, "RX"),
+  (ModsFlag::HALFTIME, "HT"),
+  (ModsFlag::NIGHTCORE, "NC"),
+  (ModsFlag::FLASHLIGHT, "FL"),
+  (ModsFlag::AUTOPLAY, "AT"),
+  (ModsFlag::SPUNOUT, "SO"),
+  (ModsFlag::RELAX2, "AP"),
+  (ModsFlag::PERFECT, "PF"),
+  (ModsFlag::KEY4, "4K"),
+  (ModsFlag::KEY5, "5K"),
+  (ModsFlag::KEY6, "6K"),
+  (ModsFlag::KEY7, "7K"),
+  (ModsFlag::KEY8, "8K"),
+  (ModsFlag::FADEIN, "FI"),
+  (ModsFlag::RANDOM, "RD"),
+  (ModsFlag::CINEMA, "CN"),
+  (ModsFlag::TARGET, "TP"),
+  (ModsFlag::KEY9, "9K"),
+  (ModsFlag::KEYCOOP, "DS"),
+  (ModsFlag::KEY1, "1K"),
+  (ModsFlag::KEY3, "3K"),
+  (ModsFlag::KEY2, "2K"),
+  (ModsFlag::SCOREV2, "SV2"),
+  (ModsFlag::MIRROR, "MR"),
+];
+
+impl ModsFlag {
+  /// Acronyms of the mods, like `["HD", "DT"]`. Nightcore and Perfect don't come with the
+  /// Double Time and Sudden Death they imply.
+  pub fn acronyms(&self) -> Vec<&'static str> {
+    // Mods implied by Nightcore and Perfect
+    let mut implied = Self::NONE;
+    if self.contains(Self::NIGHTCORE) {
+      implied.insert(Self::DOUBLETIME);
+    }
+    if self.contains(Self::PERFECT) {
+      implied.insert(Self::SUDDENDEATH);
+    }
+
+    MODS_ACRONYMS
+      .iter()
+      .filter(|(flag, _)| self.contains(*flag) && !implied.contains(*flag))
+      .map(|(_, acronym)| *acronym)
+      .collect()
+  }
+
+  /// Parse the acronym of a mod, case insensitive. Legacy acronyms `CO` and `V2` are accepted.
+  pub fn from_acronym(acronym: &str) -> Option<Self> {
+    let acronym = acronym.to_ascii_uppercase();
+    match acronym.as_str() {
+      "CO" => Some(Self::KEYCOOP),
+      "V2" => Some(Self::SCOREV2),
+      _ => MODS_ACRONYMS
+        .iter()
+        .find(|(_, name)| *name == acronym)
+        .map(|(flag, _)| *flag),
+    }
+  }
+}
+
+#[test]
+fn test_mods_acronyms() {
+  let mods = ModsFlag::HIDDEN | ModsFlag::NIGHTCORE | ModsFlag::PERFECT;
+  assert_eq!(mods.acronyms(), ["HD", "NC", "PF"]);
+  assert!(ModsFlag::NONE.acronyms().is_empty());
+
+  assert_eq!(ModsFlag::from_acronym("dt"), Some(ModsFlag::DOUBLETIME));
+  assert_eq!(ModsFlag::from_acronym("NC"), Some(ModsFlag::NIGHTCORE));
+  assert_eq!(ModsFlag::from_acronym("V2"), Some(ModsFlag::SCOREV2));
+  assert_eq!(ModsFlag::from_acronym("XX"), None);
+}
+
+#[cfg(any(feature = "v1", feature = "v2"))]
+#[test]
+fn test_response_helpers() {
+  use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+  let mut headers = HeaderMap::new();
+  assert_eq!(retry_after(&headers), None);
+  headers.insert(RETRY_AFTER, HeaderValue::from_static("3"));
+  assert_eq!(
+    retry_after(&headers),
+    Some(std::time::Duration::from_secs(3))
+  );
+  // HTTP date form is not supported
+  headers.insert(
+    RETRY_AFTER,
+    HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+  );
+  assert_eq!(retry_after(&headers), None);
+
+  assert_eq!(
+    error_message(br#"{"error": "invalid cursor"}"#).as_deref(),
+    Some("invalid cursor")
+  );
+  assert_eq!(error_message(br#"{"error": null}"#), None);
+  assert_eq!(error_message(b"[]"), None);
+}
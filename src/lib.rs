@@ -18,13 +18,21 @@ macro_rules! cfg_v2 {
 
 cfg_v1! {
     pub mod api_v1;
+    // Kept for compatibility, `api` always refers to the v1 API so that both
+    // versions can be enabled together.
     pub use api_v1 as api;
 }
 
 cfg_v2! {
     pub mod api_v2;
-    pub use api_v2 as api;
 }
 
+pub mod common;
+
+pub mod ratelimit;
+
 #[cfg(feature = "util")]
 pub mod util;
+
+#[cfg(all(test, any(feature = "v1", feature = "v2")))]
+mod stub;
@@ -0,0 +1,142 @@
+//! Client side rate limiting, shared by all API versions.
+
+use std::time::Duration;
+
+use tokio::{sync::Mutex, time::Instant};
+
+/// A token bucket rate limiter. The bucket holds at most `capacity` tokens and refills one
+/// token every `period / capacity`. Every request takes one token, and waits when the bucket
+/// is empty.
+///
+/// Callers are queued in the order they call [`RateLimiter::acquire`], so concurrent requests
+/// are served fairly.
+#[derive(Debug)]
+pub struct RateLimiter {
+  capacity: u32,
+  interval: Duration,
+  bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+  tokens: u32,
+  last_refill: Instant,
+}
+
+impl RateLimiter {
+  /// Allow `capacity` requests in every `period`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `capacity` is zero, or `period` is shorter than `capacity` nanoseconds, which
+  /// leaves no time between two tokens.
+  pub fn new(capacity: u32, period: Duration) -> Self {
+    assert!(capacity > 0, "rate limiter capacity must be positive");
+    let interval = period / capacity;
+    assert!(
+      !interval.is_zero(),
+      "rate limiter period must be at least capacity nanoseconds"
+    );
+
+    Self {
+      capacity,
+      interval,
+      bucket: Mutex::new(Bucket {
+        tokens: capacity,
+        last_refill: Instant::now(),
+      }),
+    }
+  }
+
+  /// Allow `capacity` requests per minute, the osu! v1 API allows about 60.
+  pub fn per_minute(capacity: u32) -> Self {
+    Self::new(capacity, Duration::from_secs(60))
+  }
+
+  /// Wait until a token is available and take it
+  pub async fn acquire(&self) {
+    // Holding the lock while sleeping keeps the later callers queued behind
+    let mut bucket = self.bucket.lock().await;
+    self.refill(&mut bucket);
+
+    if bucket.tokens == 0 {
+      tokio::time::sleep_until(bucket.last_refill + self.interval).await;
+      self.refill(&mut bucket);
+    }
+
+    bucket.tokens -= 1;
+  }
+
+  fn refill(&self, bucket: &mut Bucket) {
+    let elapsed = bucket.last_refill.elapsed();
+    // Clamp before narrowing, a long idle time may overflow u32 with a short interval
+    let new_tokens =
+      (elapsed.as_nanos() / self.interval.as_nanos()).min(self.capacity as u128) as u32;
+    if new_tokens == 0 {
+      return;
+    }
+
+    if bucket.tokens + new_tokens >= self.capacity {
+      bucket.tokens = self.capacity;
+      bucket.last_refill = Instant::now();
+    } else {
+      bucket.tokens += new_tokens;
+      bucket.last_refill += self.interval * new_tokens;
+    }
+  }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limiter() {
+  let limiter = RateLimiter::new(2, Duration::from_secs(1));
+  let start = Instant::now();
+
+  // burst of the whole capacity doesn't wait
+  limiter.acquire().await;
+  limiter.acquire().await;
+  assert_eq!(start.elapsed(), Duration::ZERO);
+
+  limiter.acquire().await;
+  assert_eq!(start.elapsed(), Duration::from_millis(500));
+  limiter.acquire().await;
+  assert_eq!(start.elapsed(), Duration::from_millis(1000));
+
+  // refill no more than capacity after idle
+  tokio::time::sleep(Duration::from_secs(10)).await;
+  let start = Instant::now();
+  for _ in 0..3 {
+    limiter.acquire().await;
+  }
+  assert_eq!(start.elapsed(), Duration::from_millis(500));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limiter_fairness() {
+  use std::sync::Arc;
+
+  let limiter = Arc::new(RateLimiter::new(1, Duration::from_secs(1)));
+  let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+  let mut tasks = Vec::new();
+  for i in 0..4 {
+    let limiter = limiter.clone();
+    let order = order.clone();
+    tasks.push(tokio::spawn(async move {
+      limiter.acquire().await;
+      order.lock().unwrap().push(i);
+    }));
+    // let the task enter the queue before spawning the next one
+    tokio::task::yield_now().await;
+  }
+  for task in tasks {
+    task.await.unwrap();
+  }
+
+  assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "at least capacity nanoseconds")]
+fn test_rate_limiter_zero_interval() {
+  RateLimiter::new(10, Duration::ZERO);
+}
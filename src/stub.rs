@@ -0,0 +1,219 @@
+//! A minimal HTTP server for testing requests against a local stand-in of the API server.
+
+use std::{
+  net::SocketAddr,
+  sync::{Arc, Mutex},
+};
+
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpListener,
+};
+
+/// Request received by the stub server
+#[derive(Debug, Clone)]
+pub(crate) struct StubRequest {
+  pub method: String,
+  /// Path with query string, like `/api/get_user?u=2`
+  pub path: String,
+  // Request headers and body are only checked by the v2 tests
+  /// Headers with lowercase names
+  #[cfg_attr(not(feature = "v2"), allow(dead_code))]
+  pub headers: Vec<(String, String)>,
+  #[cfg_attr(not(feature = "v2"), allow(dead_code))]
+  pub body: String,
+  /// Time the request was fully read, on the tokio clock so paused tests can check it
+  #[cfg_attr(not(feature = "v2"), allow(dead_code))]
+  pub received_at: tokio::time::Instant,
+}
+
+impl StubRequest {
+  #[cfg_attr(not(feature = "v2"), allow(dead_code))]
+  pub fn header(&self, key: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(name, _)| name == key)
+      .map(|(_, val)| val.as_str())
+  }
+}
+
+/// Response of the stub server, in status code and JSON body
+pub(crate) struct StubResponse {
+  pub status: u16,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl StubResponse {
+  pub fn json(status: u16, body: impl Into<String>) -> Self {
+    Self {
+      status,
+      headers: Vec::new(),
+      body: body.into(),
+    }
+  }
+
+  // Only the v1 tests respond with extra headers
+  #[cfg_attr(not(feature = "v1"), allow(dead_code))]
+  pub fn header(mut self, key: &str, val: &str) -> Self {
+    self.headers.push((key.to_string(), val.to_string()));
+    self
+  }
+}
+
+pub(crate) struct StubServer {
+  pub addr: SocketAddr,
+  requests: Arc<Mutex<Vec<StubRequest>>>,
+}
+
+impl StubServer {
+  /// Start the server on a random local port, every request is answered by the handler
+  pub async fn start<F>(handler: F) -> Self
+  where
+    F: Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+  {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let handler = Arc::new(handler);
+
+    let recorded = requests.clone();
+    tokio::spawn(async move {
+      loop {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let handler = handler.clone();
+        let recorded = recorded.clone();
+        tokio::spawn(async move {
+          let Some(req) = read_request(&mut stream).await else {
+            return;
+          };
+          let resp = handler(&req);
+          recorded.lock().unwrap().push(req);
+
+          let mut raw = format!(
+            "HTTP/1.1 {} STUB\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            resp.status,
+            resp.body.len()
+          );
+          for (key, val) in resp.headers {
+            raw.push_str(&format!("{key}: {val}\r\n"));
+          }
+          raw.push_str("\r\n");
+          raw.push_str(&resp.body);
+          let _ = stream.write_all(raw.as_bytes()).await;
+        });
+      }
+    });
+
+    Self { addr, requests }
+  }
+
+  /// URL of the given path on this server
+  pub fn url(&self, path: &str) -> reqwest::Url {
+    reqwest::Url::parse(&format!("http://{}{path}", self.addr)).unwrap()
+  }
+
+  pub fn requests(&self) -> Vec<StubRequest> {
+    self.requests.lock().unwrap().clone()
+  }
+}
+
+/// Start a stub server answering every request with the handler, along with a v1 client
+/// sending requests to it with the key `stub-key`
+#[cfg(feature = "v1")]
+pub(crate) async fn stub_v1_client<F>(handler: F) -> (StubServer, crate::api_v1::OsuClient)
+where
+  F: Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+{
+  let server = StubServer::start(handler).await;
+  let client = crate::api_v1::OsuClient::builder()
+    .api_key("stub-key")
+    .base_url(server.url("/api/"))
+    .build();
+
+  (server, client)
+}
+
+/// Start a stub server that answers the token endpoint with a token lasting a day, other
+/// requests are answered by the handler
+#[cfg(feature = "v2")]
+pub(crate) async fn stub_v2_server<F>(handler: F) -> StubServer
+where
+  F: Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+{
+  StubServer::start(move |req| {
+    if req.path == "/oauth/token" {
+      return StubResponse::json(
+        200,
+        r#"{"token_type": "Bearer", "expires_in": 86400, "access_token": "token"}"#,
+      );
+    }
+    handler(req)
+  })
+  .await
+}
+
+/// Start a stub server like [`stub_v2_server`], along with a v2 client sending requests to it
+#[cfg(feature = "v2")]
+pub(crate) async fn stub_v2_client<F>(handler: F) -> (StubServer, crate::api_v2::OsuClient)
+where
+  F: Fn(&StubRequest) -> StubResponse + Send + Sync + 'static,
+{
+  let server = stub_v2_server(handler).await;
+  let client = crate::api_v2::OsuClient::builder()
+    .client_id(1234)
+    .client_secret("secret")
+    .base_url(server.url("/"))
+    .build();
+
+  (server, client)
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> Option<StubRequest> {
+  let mut buf = Vec::new();
+  let mut chunk = [0_u8; 1024];
+
+  let header_end = loop {
+    let n = stream.read(&mut chunk).await.ok()?;
+    if n == 0 {
+      return None;
+    }
+    buf.extend_from_slice(&chunk[..n]);
+    if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+      break pos + 4;
+    }
+  };
+
+  let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+  let mut lines = head.split("\r\n");
+  let mut request_line = lines.next()?.split(' ');
+  let method = request_line.next()?.to_string();
+  let path = request_line.next()?.to_string();
+  let headers: Vec<(String, String)> = lines
+    .filter_map(|line| line.split_once(':'))
+    .map(|(key, val)| (key.trim().to_lowercase(), val.trim().to_string()))
+    .collect();
+
+  let length: usize = headers
+    .iter()
+    .find(|(key, _)| key == "content-length")
+    .and_then(|(_, val)| val.parse().ok())
+    .unwrap_or(0);
+  while buf.len() < header_end + length {
+    let n = stream.read(&mut chunk).await.ok()?;
+    if n == 0 {
+      return None;
+    }
+    buf.extend_from_slice(&chunk[..n]);
+  }
+  let body = String::from_utf8_lossy(&buf[header_end..header_end + length]).to_string();
+
+  Some(StubRequest {
+    method,
+    path,
+    headers,
+    body,
+    received_at: tokio::time::Instant::now(),
+  })
+}
@@ -28,11 +28,34 @@ where
   C: OsuApiRequester,
   U: Into<UserId<'u>>,
 {
-  let prop = GetUserRecentProp::builder()
-    .api_key(key)
+  latest_replay(client, Some(key), user).await
+}
+
+/// Get the latest replay from the given user without passing the API key. The requester must
+/// supply the key itself, like [`OsuClient`](crate::api_v1::OsuClient) or the decorators wrapping it, such as
+/// `Cached<OsuClient>`.
+pub async fn get_client_latest_replay<'u, C, U>(client: &C, user: U) -> Result<LatestReplay, Error>
+where
+  C: OsuApiRequester,
+  U: Into<UserId<'u>>,
+{
+  latest_replay(client, None, user).await
+}
+
+async fn latest_replay<'u, C, U>(
+  client: &C,
+  key: Option<&str>,
+  user: U,
+) -> Result<LatestReplay, Error>
+where
+  C: OsuApiRequester,
+  U: Into<UserId<'u>>,
+{
+  let mut prop = GetUserRecentProp::builder()
     .user_info(user)
     .limit(1)
     .build();
+  prop.api_key = key;
 
   let mut resp = client.get_user_recent(prop).await?;
 
@@ -42,11 +65,11 @@ where
 
   let user_recent = resp.swap_remove(0);
 
-  let prop = GetBeatmapsProps::builder()
-    .api_key(key)
+  let mut prop = GetBeatmapsProps::builder()
     .beatmap_id(user_recent.beatmap_id)
     .limit(1)
     .build();
+  prop.api_key = key;
 
   let mut resp = client.get_beatmaps(prop).await?;
 